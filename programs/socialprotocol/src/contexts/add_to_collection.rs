@@ -0,0 +1,46 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct AddToCollection<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // get a post account, no way to add constraint because shdw (hash) is unknown
+    #[account(constraint = post.st == POST_STATUS_ACTIVE @ SplingError::PostNotPublished)]
+    pub post: Account<'info, Post>,
+    // increase the collection account size by one public key
+    #[account(mut, seeds = [b"collection", user_profile.uid.to_le_bytes().as_ref(), name.as_bytes()], bump = collection.bump, realloc = Collection::space(&name, collection.posts.len() + 1), realloc::payer = user, realloc::zero = false)]
+    pub collection: Account<'info, Collection>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddToCollection<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self {
+            post, collection, ..
+        } = self;
+
+        require!(
+            collection.posts.len() < MAX_COLLECTION_POSTS,
+            SplingError::CollectionFull
+        );
+        require!(
+            !collection.posts.contains(&post.key()),
+            SplingError::AlreadyInCollection
+        );
+
+        // new posts go to the end, reorder to move them
+        collection.posts.push(post.key());
+
+        let clock: Clock = Clock::get().unwrap();
+        collection.ts = clock.unix_timestamp;
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use crate::*;
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(name: String, address: u32)]
+pub struct AddToList<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"spling"], bump = spling.bump)]
+    pub spling: Account<'info, Spling>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"list", user_profile.uid.to_le_bytes().as_ref(), name.as_bytes()], bump = list.bump)]
+    pub list: Account<'info, List>,
+    // a user is on a list once, adding twice fails on the existing account
+    #[account(init, payer = user, space = 8 + mem::size_of::<ListMember>(), seeds = [b"list_member", list.key().as_ref(), address.to_le_bytes().as_ref()], bump)]
+    pub list_member: Account<'info, ListMember>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddToList<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        address: u32,
+        bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self {
+            spling,
+            list,
+            list_member,
+            ..
+        } = self;
+
+        require!(
+            address != 0 && address <= spling.users,
+            SplingError::InvalidUser
+        );
+
+        let clock: Clock = Clock::get().unwrap();
+        list_member.ts = clock.unix_timestamp;
+
+        list_member.list = list.key();
+        list_member.uid = address;
+
+        // ListMember is a PDA, so here we store the bump
+        list_member.bump = bump;
+
+        list.members += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AddWallet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // the new wallet signs as well, so only wallets the user holds can be added
+    pub new_wallet: Signer<'info>,
+    // only the primary wallet manages the other wallets
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, has_one = user)]
+    pub user_profile: Account<'info, UserProfile>,
+    // a wallet belongs to one profile, adding a wallet that is in use fails here
+    #[account(init, payer = user, space = 8 + mem::size_of::<Wallet>(), seeds = [b"wallet", new_wallet.key().as_ref()], bump)]
+    pub wallet: Account<'info, Wallet>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddWallet<'_> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let Self {
+            new_wallet,
+            user_profile,
+            wallet,
+            ..
+        } = self;
+
+        let slot = user_profile
+            .wallets
+            .iter()
+            .position(|w| *w == Pubkey::default())
+            .ok_or(SplingError::WalletsFull)?;
+        user_profile.wallets[slot] = new_wallet.key();
+
+        wallet.uid = user_profile.uid;
+        wallet.wallet = new_wallet.key();
+
+        // Wallet is a PDA, so here we store the bump
+        wallet.bump = bump;
+        Ok(())
+    }
+}
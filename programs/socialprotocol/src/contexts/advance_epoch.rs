@@ -0,0 +1,61 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    // anyone can end an epoch once it lasted long enough, e.g. a clockwork thread
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut, seeds = [b"reward_pool"], bump = reward_pool.bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    #[account(seeds = [b"reward_vault"], bump)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<RewardEpoch>(), seeds = [b"reward_epoch", reward_pool.epoch.to_le_bytes().as_ref()], bump)]
+    pub reward_epoch: Box<Account<'info, RewardEpoch>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AdvanceEpoch<'_> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let Self {
+            reward_pool,
+            reward_vault,
+            reward_epoch,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            clock.unix_timestamp >= reward_pool.epoch_start + reward_pool.epoch_length,
+            SplingError::EpochNotOver
+        );
+
+        // only tokens that are not owed to earlier epochs are paid out, nothing without points
+        let available = reward_vault.amount.saturating_sub(reward_pool.reserved);
+        let emission = match reward_pool.points {
+            0 => 0,
+            _ => reward_pool.emission.min(available),
+        };
+
+        reward_epoch.epoch = reward_pool.epoch;
+        reward_epoch.points = reward_pool.points;
+        reward_epoch.emission = emission;
+        reward_epoch.claimed = 0;
+
+        // RewardEpoch is a PDA, so here we store the bump
+        reward_epoch.bump = bump;
+
+        reward_pool.reserved += emission;
+        reward_pool.epoch += 1;
+        reward_pool.epoch_start = clock.unix_timestamp;
+        reward_pool.points = 0;
+
+        emit!(EpochAdvanced {
+            epoch: reward_epoch.epoch,
+            points: reward_epoch.points,
+            emission,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(follower: u32)]
+pub struct ApproveFollowRequest<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"follow_request", user_profile.uid.to_le_bytes().as_ref(), follower.to_le_bytes().as_ref()], bump = follow_request.bump, constraint = follow_request.status == FOLLOW_REQUEST_PENDING @ SplingError::FollowRequestNotPending)]
+    pub follow_request: Account<'info, FollowRequest>,
+    // the approved user starts following, as with follow_user
+    #[account(mut, seeds = [b"user_profile", follow_request.follower.to_le_bytes().as_ref()], bump = follower_profile.bump)]
+    pub follower_profile: Account<'info, UserProfile>,
+}
+
+impl<'info> ApproveFollowRequest<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            user_profile,
+            follow_request,
+            follower_profile,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        follow_request.ts = clock.unix_timestamp;
+        follow_request.status = FOLLOW_REQUEST_APPROVED;
+
+        // add user id to vector
+        follower_profile.following.push(user_profile.uid);
+
+        emit!(FollowApproved {
+            uid: user_profile.uid,
+            follower: follow_request.follower,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
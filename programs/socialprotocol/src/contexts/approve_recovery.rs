@@ -0,0 +1,70 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(guardian: u8)]
+pub struct ApproveRecovery<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"guardians", guardians.uid.to_le_bytes().as_ref()], bump = guardians.bump)]
+    pub guardians: Account<'info, Guardians>,
+    #[account(mut, seeds = [b"recovery", guardians.uid.to_le_bytes().as_ref(), recovery.index.to_le_bytes().as_ref()], bump = recovery.bump, constraint = recovery.status == RECOVERY_PENDING @ SplingError::RecoveryNotPending)]
+    pub recovery: Account<'info, Recovery>,
+    // one approval per guardian, a second approval fails here
+    #[account(init, payer = user, space = 8 + mem::size_of::<RecoveryApproval>(), seeds = [b"recovery_approval", recovery.key().as_ref(), guardian.to_le_bytes().as_ref()], bump)]
+    pub approval: Account<'info, RecoveryApproval>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ApproveRecovery<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        guardian: u8,
+        bump: u8,
+    ) -> Result<()> {
+        let Self {
+            user,
+            guardians,
+            recovery,
+            approval,
+            ..
+        } = self;
+
+        guardians
+            .guardians
+            .get(guardian as usize)
+            .ok_or(SplingError::NotAGuardian)?
+            .check_signer(user.key, &guardians.guardians, remaining_accounts)?;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            !recovery.is_expired(guardians.delay, clock.unix_timestamp),
+            SplingError::RecoveryExpired
+        );
+
+        approval.recovery = recovery.key();
+        approval.guardian = guardian;
+        approval.signer = user.key();
+        approval.ts = clock.unix_timestamp;
+
+        // RecoveryApproval is a PDA, so here we store the bump
+        approval.bump = bump;
+
+        // the delay starts once enough guardians approved
+        recovery.approvals += 1;
+        if recovery.approvals == guardians.threshold {
+            recovery.approved_at = clock.unix_timestamp;
+        }
+
+        emit!(RecoveryApproved {
+            uid: recovery.uid,
+            recovery: recovery.key(),
+            guardian,
+            signer: user.key(),
+            approvals: recovery.approvals,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
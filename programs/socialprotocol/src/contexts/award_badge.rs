@@ -0,0 +1,48 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(uid: u32, kind: u8)]
+pub struct AwardBadge<'info> {
+    // anyone can award a badge once it is earned, e.g. the user or an indexer
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // a badge is awarded once, a second award fails here
+    #[account(init, payer = user, space = 8 + mem::size_of::<Badge>(), seeds = [b"badge", uid.to_le_bytes().as_ref(), kind.to_le_bytes().as_ref()], bump)]
+    pub badge: Account<'info, Badge>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AwardBadge<'info> {
+    pub fn process(&mut self, uid: u32, kind: u8, bump: u8) -> Result<()> {
+        let Self {
+            user_profile,
+            badge,
+            ..
+        } = self;
+
+        require!(
+            Badge::is_earned(kind, user_profile)?,
+            SplingError::BadgeNotEarned
+        );
+
+        let clock: Clock = Clock::get().unwrap();
+
+        badge.uid = uid;
+        badge.kind = kind;
+        badge.minted = false;
+        badge.ts = clock.unix_timestamp;
+
+        // Badge is a PDA, so here we store the bump
+        badge.bump = bump;
+
+        emit!(BadgeAwarded {
+            uid,
+            kind,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::*;
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(address: u32)]
+pub struct BlockUser<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"spling"], bump = spling.bump)]
+    pub spling: Account<'info, Spling>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<Block>(), seeds = [b"block", user_profile.uid.to_le_bytes().as_ref(), address.to_le_bytes().as_ref()], bump)]
+    pub block: Account<'info, Block>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BlockUser<'_> {
+    pub fn process(&mut self, address: u32, bump: u8) -> Result<()> {
+        let Self {
+            spling,
+            user_profile,
+            block,
+            ..
+        } = self;
+
+        require!(
+            address != 0 && address <= spling.users && address != user_profile.uid,
+            SplingError::InvalidUser
+        );
+
+        let clock: Clock = Clock::get().unwrap();
+        block.ts = clock.unix_timestamp;
+
+        block.uid = user_profile.uid;
+        block.blocked = address;
+
+        // Block is a PDA, so here we store the bump
+        block.bump = bump;
+        Ok(())
+    }
+}
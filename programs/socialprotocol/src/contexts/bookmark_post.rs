@@ -0,0 +1,41 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct BookmarkPost<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // get a post account, no way to add constraint because shdw (hash) is unknown
+    #[account(constraint = post.st == POST_STATUS_ACTIVE @ SplingError::PostNotPublished)]
+    pub post: Account<'info, Post>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<Bookmark>(), seeds = [b"bookmark", user_profile.uid.to_le_bytes().as_ref(), post.key().as_ref()], bump)]
+    pub bookmark: Account<'info, Bookmark>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BookmarkPost<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>], bump: u8) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self {
+            user_profile,
+            post,
+            bookmark,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        bookmark.ts = clock.unix_timestamp;
+
+        bookmark.uid = user_profile.uid;
+        bookmark.post = post.key();
+
+        // Bookmark is a PDA, so here we store the bump
+        bookmark.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    pub user: Signer<'info>,
+    // the primary wallet can cancel, as long as the recovery is not executed. other wallets
+    // can't, a stolen secondary wallet would otherwise block every recovery
+    // anyone can cancel a recovery that wasn't approved or executed in time
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"guardians", user_profile.uid.to_le_bytes().as_ref()], bump = guardians.bump)]
+    pub guardians: Account<'info, Guardians>,
+    #[account(mut, seeds = [b"recovery", user_profile.uid.to_le_bytes().as_ref(), recovery.index.to_le_bytes().as_ref()], bump = recovery.bump, constraint = recovery.status == RECOVERY_PENDING @ SplingError::RecoveryNotPending)]
+    pub recovery: Account<'info, Recovery>,
+}
+
+impl<'info> CancelRecovery<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            user,
+            user_profile,
+            guardians,
+            recovery,
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            user_profile.user == *user.key
+                || recovery.is_expired(guardians.delay, clock.unix_timestamp),
+            SplingError::NotAuthorized
+        );
+
+        // the recovery account is kept, so the attempt stays on record
+        recovery.status = RECOVERY_CANCELLED;
+        guardians.pending = false;
+
+        emit!(RecoveryCancelled {
+            uid: recovery.uid,
+            recovery: recovery.key(),
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
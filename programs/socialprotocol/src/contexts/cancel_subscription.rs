@@ -0,0 +1,77 @@
+use crate::*;
+use clockwork_sdk::{state::Thread, ThreadProgram};
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"subscription", subscription.creator.to_le_bytes().as_ref(), user_profile.uid.to_le_bytes().as_ref()], bump = subscription.bump)]
+    pub subscription: Box<Account<'info, Subscription>>,
+    #[account(mut, seeds = [b"subscription_tier", subscription.creator.to_le_bytes().as_ref()], bump = subscription_tier.bump)]
+    pub subscription_tier: Box<Account<'info, SubscriptionTier>>,
+    // the thread balance goes back to the account that subscribed
+    #[account(mut, address = subscription.payer)]
+    /// CHECK: payer recorded on the subscription
+    pub payer: UncheckedAccount<'info>,
+    #[account(mut, address = Thread::pubkey(subscription.key(), "subscription_thread".to_string()))]
+    /// CHECK: thread account, deleted when it still exists
+    pub subscription_thread: UncheckedAccount<'info>,
+    #[account(address = ThreadProgram::id())]
+    /// CHECK: thread program, only invoked when the thread still exists
+    pub thread_program: UncheckedAccount<'info>,
+}
+
+impl<'info> CancelSubscription<'_> {
+    // the subscription stays active until the end of the paid period, the subscriber
+    // can revoke the delegate approval through the token program
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            subscription,
+            subscription_tier,
+            payer,
+            subscription_thread,
+            thread_program,
+            ..
+        } = self;
+
+        require!(
+            subscription.status == SUBSCRIPTION_ACTIVE,
+            SplingError::SubscriptionNotActive
+        );
+
+        subscription.status = SUBSCRIPTION_CANCELLED;
+        subscription_tier.subscribers = subscription_tier.subscribers.saturating_sub(1);
+
+        let clock: Clock = Clock::get().unwrap();
+        emit!(SubscriptionEnded {
+            creator: subscription.creator,
+            subscriber: subscription.subscriber,
+            status: subscription.status,
+            paid_until: subscription.paid_until,
+            ts: clock.unix_timestamp,
+        });
+
+        // no more charges, so the thread has no work left
+        if !subscription_thread.data_is_empty() {
+            let creator = subscription.creator.to_le_bytes();
+            let subscriber = subscription.subscriber.to_le_bytes();
+            clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadDelete {
+                    authority: subscription.to_account_info(),
+                    close_to: payer.to_account_info(),
+                    thread: subscription_thread.to_account_info(),
+                },
+                &[&[
+                    b"subscription",
+                    creator.as_ref(),
+                    subscriber.as_ref(),
+                    &[subscription.bump],
+                ]],
+            ))?;
+        }
+
+        Ok(())
+    }
+}
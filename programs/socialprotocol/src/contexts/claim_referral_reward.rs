@@ -0,0 +1,76 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(referee: u32)]
+pub struct ClaimReferralReward<'info> {
+    pub user: Signer<'info>,
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    // the referred user, which records who referred it
+    #[account(mut, seeds = [b"user_profile", referee.to_le_bytes().as_ref()], bump = referee_profile.bump, constraint = referee_profile.referrer == user_profile.uid @ SplingError::NotTheReferrer)]
+    pub referee_profile: Account<'info, UserProfile>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut, seeds = [b"referral_vault"], bump)]
+    pub referral_vault: Account<'info, TokenAccount>,
+    // rewards are paid to a wallet of the referrer
+    #[account(mut, constraint = token_account.mint == referral_vault.mint, constraint = user_profile.is_wallet(&token_account.owner) @ SplingError::NotAuthorized)]
+    pub token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimReferralReward<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>], vault_bump: u8) -> Result<()> {
+        let Self {
+            user_profile,
+            referee_profile,
+            config,
+            referral_vault,
+            token_account,
+            token_program,
+            ..
+        } = self;
+
+        require!(
+            !referee_profile.referral_paid,
+            SplingError::ReferralAlreadyPaid
+        );
+
+        // likes only count from users other than the referrer, so a referred account has to
+        // see real activity. the stake position of the referred user is passed in the remaining accounts
+        let referral = config.referral;
+        let staked = StakePosition::staked(remaining_accounts, referee_profile.uid)?;
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            referral.is_reached(referee_profile, staked, clock.unix_timestamp),
+            SplingError::ReferralMilestoneNotReached
+        );
+
+        let seeds: &[&[u8]] = &[b"referral_vault", &[vault_bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token::Transfer {
+                    from: referral_vault.to_account_info(),
+                    to: token_account.to_account_info(),
+                    authority: referral_vault.to_account_info(),
+                },
+                &[seeds],
+            ),
+            referral.reward,
+        )?;
+
+        referee_profile.referral_paid = true;
+        user_profile.referral_rewards = user_profile
+            .referral_rewards
+            .saturating_add(referral.reward);
+
+        emit!(ReferralRewarded {
+            referrer: user_profile.uid,
+            referee: referee_profile.uid,
+            amount: referral.reward,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(epoch: u32)]
+pub struct ClaimRewards<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"reward_pool"], bump = reward_pool.bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    // only exists once the epoch ended
+    #[account(mut, seeds = [b"reward_epoch", epoch.to_le_bytes().as_ref()], bump = reward_epoch.bump)]
+    pub reward_epoch: Box<Account<'info, RewardEpoch>>,
+    // claiming closes the points, so a share is paid once
+    #[account(mut, seeds = [b"creator_points", user_profile.uid.to_le_bytes().as_ref(), epoch.to_le_bytes().as_ref()], bump = creator_points.bump, close = payer)]
+    pub creator_points: Box<Account<'info, CreatorPoints>>,
+    // rent goes back to the account that created the points
+    #[account(mut, address = creator_points.payer)]
+    /// CHECK: payer recorded on the points
+    pub payer: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"reward_vault"], bump)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    // rewards are paid to a wallet of the creator
+    #[account(mut, constraint = token_account.mint == reward_vault.mint, constraint = user_profile.is_wallet(&token_account.owner) @ SplingError::NotAuthorized)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimRewards<'_> {
+    pub fn process(&mut self, vault_bump: u8) -> Result<()> {
+        let Self {
+            user_profile,
+            reward_pool,
+            reward_epoch,
+            creator_points,
+            reward_vault,
+            token_account,
+            token_program,
+            ..
+        } = self;
+
+        // pro rata share of the emission of the epoch, rounded down
+        let amount = match reward_epoch.points {
+            0 => 0,
+            total => {
+                (reward_epoch.emission as u128 * creator_points.points as u128 / total as u128)
+                    as u64
+            }
+        };
+
+        if amount > 0 {
+            let seeds: &[&[u8]] = &[b"reward_vault", &[vault_bump]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    token::Transfer {
+                        from: reward_vault.to_account_info(),
+                        to: token_account.to_account_info(),
+                        authority: reward_vault.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+            )?;
+        }
+
+        reward_epoch.claimed += amount;
+        reward_pool.reserved = reward_pool.reserved.saturating_sub(amount);
+
+        let clock: Clock = Clock::get().unwrap();
+        emit!(RewardsClaimed {
+            uid: user_profile.uid,
+            epoch: reward_epoch.epoch,
+            points: creator_points.points,
+            amount,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::*;
+use clockwork_sdk::{state::Thread, ThreadProgram};
+
+#[derive(Accounts)]
+pub struct ChargeSubscription<'info> {
+    #[account(mut, seeds = [b"subscription", subscription.creator.to_le_bytes().as_ref(), subscription.subscriber.to_le_bytes().as_ref()], bump = subscription.bump)]
+    pub subscription: Box<Account<'info, Subscription>>,
+    #[account(mut, seeds = [b"subscription_tier", subscription.creator.to_le_bytes().as_ref()], bump = subscription_tier.bump)]
+    pub subscription_tier: Box<Account<'info, SubscriptionTier>>,
+    // the subscriber may have closed or emptied the account, that lapses the subscription
+    #[account(mut, address = subscription.token_account)]
+    /// CHECK: subscriber token account, checked before it is charged
+    pub token_account: UncheckedAccount<'info>,
+    #[account(mut, address = subscription.recipient)]
+    pub recipient: Box<Account<'info, TokenAccount>>,
+    #[account(seeds = [b"subscription_delegate"], bump)]
+    /// CHECK: delegate PDA, holds no data
+    pub delegate: UncheckedAccount<'info>,
+    // the thread balance goes back to the account that subscribed
+    #[account(mut, address = subscription.payer)]
+    /// CHECK: payer recorded on the subscription
+    pub payer: UncheckedAccount<'info>,
+    // called by the thread every day, or by anyone
+    #[account(mut, address = Thread::pubkey(subscription.key(), "subscription_thread".to_string()))]
+    /// CHECK: thread account, signs when the thread runs this instruction
+    pub subscription_thread: UncheckedAccount<'info>,
+    #[account(address = ThreadProgram::id())]
+    /// CHECK: thread program, only invoked when the thread still exists
+    pub thread_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ChargeSubscription<'_> {
+    pub fn process(&mut self, delegate_bump: u8) -> Result<()> {
+        let Self {
+            subscription,
+            subscription_tier,
+            token_account,
+            recipient,
+            delegate,
+            payer,
+            subscription_thread,
+            thread_program,
+            token_program,
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        if subscription.status == SUBSCRIPTION_ACTIVE {
+            // the thread fires daily, nothing is due until the paid period ends
+            if subscription.is_active(clock.unix_timestamp) {
+                return Ok(());
+            }
+            if subscription.can_charge(token_account, &delegate.key()) {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        token::Transfer {
+                            from: token_account.to_account_info(),
+                            to: recipient.to_account_info(),
+                            authority: delegate.to_account_info(),
+                        },
+                        &[&[b"subscription_delegate", &[delegate_bump]]],
+                    ),
+                    subscription.price,
+                )?;
+
+                subscription.allowance -= subscription.price;
+                subscription.extend(clock.unix_timestamp);
+
+                emit!(SubscriptionCharged {
+                    creator: subscription.creator,
+                    subscriber: subscription.subscriber,
+                    amount: subscription.price,
+                    paid_until: subscription.paid_until,
+                    ts: clock.unix_timestamp,
+                });
+                return Ok(());
+            }
+
+            // without balance or approval the subscription lapses
+            subscription.status = SUBSCRIPTION_LAPSED;
+            subscription_tier.subscribers = subscription_tier.subscribers.saturating_sub(1);
+
+            emit!(SubscriptionEnded {
+                creator: subscription.creator,
+                subscriber: subscription.subscriber,
+                status: subscription.status,
+                paid_until: subscription.paid_until,
+                ts: clock.unix_timestamp,
+            });
+        }
+
+        // the thread program can't be called back from the thread it is running, so the thread
+        // only lapses the subscription, and anyone deletes the thread of an ended subscription
+        if !subscription_thread.is_signer && !subscription_thread.data_is_empty() {
+            let creator = subscription.creator.to_le_bytes();
+            let subscriber = subscription.subscriber.to_le_bytes();
+            clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadDelete {
+                    authority: subscription.to_account_info(),
+                    close_to: payer.to_account_info(),
+                    thread: subscription_thread.to_account_info(),
+                },
+                &[&[
+                    b"subscription",
+                    creator.as_ref(),
+                    subscriber.as_ref(),
+                    &[subscription.bump],
+                ]],
+            ))?;
+        }
+
+        Ok(())
+    }
+}
@@ -6,18 +6,89 @@ pub struct ClockworkDeletePost<'info> {
     pub shdw: SystemAccount<'info>,
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
-    #[account(mut,close=spling, seeds = [b"post".as_ref(), shdw.key().as_ref()], bump)]
+    #[account(mut, seeds = [b"post".as_ref(), shdw.key().as_ref()], bump = post.bump)]
     pub post: Account<'info, Post>,
-    #[account(mut,signer,address = Thread::pubkey(post.key(),"post_thread".to_string()))]
-    pub post_thread: Box<Account<'info, Thread>>,
+    #[account(mut, seeds = [b"likes".as_ref(), post.key().as_ref()], bump = likes.bump)]
+    pub likes: Account<'info, Likes>,
+    // tags of the post no longer count towards the trending leaderboard
+    #[account(mut, seeds = [b"trending"], bump = trending.bump)]
+    pub trending: Box<Account<'info, Trending>>,
+    // rent goes back to the account that paid for the post
+    #[account(mut, address = post.payer)]
+    /// CHECK: payer recorded on the post
+    pub payer: UncheckedAccount<'info>,
+    // the post no longer counts towards the live posts of the author
+    #[account(mut, address = UserProfile::pubkey(post.uid))]
+    /// CHECK: profile of the author, may not exist
+    pub author_profile: UncheckedAccount<'info>,
+    // called by the expiry thread at the expiry timestamp, or by anyone after it
+    #[account(mut, address = Thread::pubkey(post.key(), "post_thread".to_string()))]
+    /// CHECK: thread account, signs when the thread runs this instruction
+    pub post_thread: UncheckedAccount<'info>,
     #[account(address = ThreadProgram::id())]
-    pub thread_program: Program<'info, ThreadProgram>,
+    /// CHECK: thread program, only invoked outside of the thread
+    pub thread_program: UncheckedAccount<'info>,
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> ClockworkDeletePost<'_> {
-    pub fn process(&mut self) -> Result<()> {
+impl<'info> ClockworkDeletePost<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let Self {
+            shdw,
+            post,
+            likes,
+            trending,
+            payer,
+            author_profile,
+            post_thread,
+            thread_program,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            post.is_expired(clock.unix_timestamp),
+            SplingError::PostNotExpired
+        );
+
+        // the thread program can't be called back from the thread it is running, so the
+        // thread only marks the post expired, and anyone closes it and its thread afterwards
+        if post.is_live() {
+            UserProfile::update(author_profile, |author| {
+                author.live_posts = author.live_posts.saturating_sub(1)
+            })?;
+        }
+        if post_thread.is_signer {
+            require!(post.st != POST_STATUS_EXPIRED, SplingError::PostNotExpired);
+            post.st = POST_STATUS_EXPIRED;
+            return Ok(());
+        }
+
+        if !post_thread.data_is_empty() {
+            clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadDelete {
+                    authority: post.to_account_info(),
+                    close_to: payer.to_account_info(),
+                    thread: post_thread.to_account_info(),
+                },
+                &[&[b"post", shdw.key().as_ref(), &[post.bump]]],
+            ))?;
+        }
+
+        // tag accounts and their post index entries are passed in the remaining accounts
+        post.remove_tags(
+            post.key(),
+            trending,
+            remaining_accounts,
+            &payer.to_account_info(),
+            clock.unix_timestamp,
+        )?;
+
+        post.close(payer.to_account_info())?;
+        likes.close(payer.to_account_info())?;
+
         Ok(())
     }
 }
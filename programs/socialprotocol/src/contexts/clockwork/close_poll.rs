@@ -0,0 +1,80 @@
+use crate::*;
+use clockwork_sdk::{state::Thread, ThreadProgram};
+
+#[derive(Accounts)]
+pub struct ClosePoll<'info> {
+    pub shdw: SystemAccount<'info>,
+    // the post may be deleted already, it's only used to sign for the thread
+    #[account(seeds = [b"post".as_ref(), shdw.key().as_ref()], bump)]
+    /// CHECK: post account of the poll, not read
+    pub post: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"poll".as_ref(), post.key().as_ref()], bump = poll.bump)]
+    pub poll: Box<Account<'info, Poll>>,
+    // the thread balance goes back to the account that paid for the poll
+    #[account(mut, address = poll.payer)]
+    /// CHECK: payer recorded on the poll
+    pub payer: UncheckedAccount<'info>,
+    // called by the thread at the close timestamp, or by anyone after it
+    #[account(mut, address = Thread::pubkey(post.key(), "poll_thread".to_string()))]
+    /// CHECK: thread account, signs when the thread runs this instruction
+    pub poll_thread: UncheckedAccount<'info>,
+    #[account(address = ThreadProgram::id())]
+    /// CHECK: thread program, only invoked when the thread still exists
+    pub thread_program: UncheckedAccount<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClosePoll<'_> {
+    pub fn process(&mut self, post_bump: u8) -> Result<()> {
+        let Self {
+            shdw,
+            post,
+            poll,
+            payer,
+            poll_thread,
+            thread_program,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        if poll.closed {
+            // calling it again on a closed poll only deletes the thread that closed it
+            require!(
+                !poll_thread.is_signer && !poll_thread.data_is_empty(),
+                SplingError::PollClosed
+            );
+        } else {
+            require!(
+                poll.closes_at != 0 && clock.unix_timestamp >= poll.closes_at,
+                SplingError::PollStillOpen
+            );
+
+            poll.closed = true;
+
+            emit!(PollClosed {
+                post: post.key(),
+                poll: poll.key(),
+                tallies: poll.tallies,
+                voters: poll.voters,
+                ts: clock.unix_timestamp,
+            });
+        }
+
+        // the thread only fires once, so it has no work left. the thread program can't be
+        // called back from the thread it is running, so then a second call deletes it
+        if !poll_thread.is_signer && !poll_thread.data_is_empty() {
+            clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadDelete {
+                    authority: post.to_account_info(),
+                    close_to: payer.to_account_info(),
+                    thread: poll_thread.to_account_info(),
+                },
+                &[&[b"post", shdw.key().as_ref(), &[post_bump]]],
+            ))?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,9 @@
+pub mod charge_subscription;
 pub mod clockwork_delete_post;
+pub mod close_poll;
+pub mod publish_post;
 
+pub use charge_subscription::*;
 pub use clockwork_delete_post::*;
+pub use close_poll::*;
+pub use publish_post::*;
@@ -0,0 +1,90 @@
+use crate::*;
+use clockwork_sdk::{state::Thread, ThreadProgram};
+
+#[derive(Accounts)]
+pub struct PublishPost<'info> {
+    pub shdw: SystemAccount<'info>,
+    #[account(mut, seeds = [b"spling"], bump = spling.bump)]
+    pub spling: Account<'info, Spling>,
+    #[account(mut, seeds = [b"post".as_ref(), shdw.key().as_ref()], bump = post.bump)]
+    pub post: Account<'info, Post>,
+    // tags of the post count towards the trending leaderboard from now on
+    #[account(mut, seeds = [b"trending"], bump = trending.bump)]
+    pub trending: Box<Account<'info, Trending>>,
+    // the thread balance goes back to the account that paid for the post
+    #[account(mut, address = post.payer)]
+    /// CHECK: payer recorded on the post
+    pub payer: UncheckedAccount<'info>,
+    // called by the publish thread at the scheduled time, or by anyone after it
+    #[account(mut, address = Thread::pubkey(post.key(), "publish_thread".to_string()))]
+    /// CHECK: thread account, signs when the thread runs this instruction
+    pub publish_thread: UncheckedAccount<'info>,
+    #[account(address = ThreadProgram::id())]
+    /// CHECK: thread program, only invoked outside of the thread
+    pub thread_program: UncheckedAccount<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PublishPost<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let Self {
+            shdw,
+            spling,
+            post,
+            trending,
+            payer,
+            publish_thread,
+            thread_program,
+            ..
+        } = self;
+
+        require!(
+            post.st == POST_STATUS_SCHEDULED,
+            SplingError::PostNotScheduled
+        );
+
+        // while scheduled, the timestamp (ts) is the scheduled time
+        let clock: Clock = Clock::get().unwrap();
+        require!(clock.unix_timestamp >= post.ts, SplingError::PostNotDue);
+
+        // the post gets its id and timestamp at the moment it goes live
+        post.ts = clock.unix_timestamp;
+
+        post.pid = &spling.posts + 1;
+
+        // increment post spling with 1, to reflect the newly published post
+        spling.posts += 1;
+
+        post.st = POST_STATUS_ACTIVE;
+
+        // tag accounts were passed to the thread at schedule time
+        post.credit_tags(trending, remaining_accounts, clock.unix_timestamp)?;
+
+        emit!(PostPublished {
+            post: post.key(),
+            pid: post.pid,
+            uid: post.uid,
+            gid: post.gid,
+            ts: post.ts,
+        });
+        // inboxes of mentioned users were passed to the thread at schedule time
+        post.emit_mentions(post.key(), remaining_accounts)?;
+
+        // the thread only fires once, so it has no work left. the thread program can't be
+        // called back from the thread it is running, so then close_post_thread deletes it
+        if !publish_thread.is_signer {
+            clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadDelete {
+                    authority: post.to_account_info(),
+                    close_to: payer.to_account_info(),
+                    thread: publish_thread.to_account_info(),
+                },
+                &[&[b"post", shdw.key().as_ref(), &[post.bump]]],
+            ))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(uid: u32, kind: u8)]
+pub struct CloseAttestation<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"attestation", uid.to_le_bytes().as_ref(), kind.to_le_bytes().as_ref()], bump = attestation.bump, close = user)]
+    pub attestation: Account<'info, Attestation>,
+}
+
+impl<'info> CloseAttestation<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        // closing the account lifts a revocation of the admin, so issuers can
+        // attest the user again
+        Ok(())
+    }
+}
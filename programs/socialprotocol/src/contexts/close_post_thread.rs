@@ -0,0 +1,52 @@
+use crate::*;
+use clockwork_sdk::{state::Thread, ThreadProgram};
+
+#[derive(Accounts)]
+#[instruction(id: String)]
+pub struct ClosePostThread<'info> {
+    // anyone can close a thread that has no work left, no signer needed besides the fee payer
+    pub shdw: SystemAccount<'info>,
+    #[account(seeds = [b"post".as_ref(), shdw.key().as_ref()], bump = post.bump)]
+    pub post: Account<'info, Post>,
+    // the thread balance goes back to the account that paid for the post
+    #[account(mut, address = post.payer)]
+    /// CHECK: payer recorded on the post
+    pub payer: UncheckedAccount<'info>,
+    #[account(mut, address = Thread::pubkey(post.key(), id))]
+    /// CHECK: thread account of the post, deleted here
+    pub thread: UncheckedAccount<'info>,
+    #[account(address = ThreadProgram::id())]
+    /// CHECK: thread program
+    pub thread_program: UncheckedAccount<'info>,
+}
+
+impl<'info> ClosePostThread<'_> {
+    // threads can't delete themselves, so the ones that ran are closed here
+    pub fn process(&mut self, id: String) -> Result<()> {
+        let Self {
+            shdw,
+            post,
+            payer,
+            thread,
+            thread_program,
+        } = self;
+
+        let done = match id.as_str() {
+            "publish_thread" => post.st != POST_STATUS_SCHEDULED,
+            _ => false,
+        };
+        require!(done, SplingError::ThreadStillNeeded);
+
+        clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+            thread_program.to_account_info(),
+            clockwork_sdk::cpi::ThreadDelete {
+                authority: post.to_account_info(),
+                close_to: payer.to_account_info(),
+                thread: thread.to_account_info(),
+            },
+            &[&[b"post", shdw.key().as_ref(), &[post.bump]]],
+        ))?;
+
+        Ok(())
+    }
+}
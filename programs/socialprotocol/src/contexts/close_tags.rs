@@ -0,0 +1,27 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CloseTags<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Account<'info, Config>,
+    // the former list of all tags, replaced by one account per tag
+    #[account(mut, seeds = [b"tags"], bump, owner = crate::ID)]
+    /// CHECK: account in the former layout, only closed
+    pub tags: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseTags<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self { user, tags, .. } = self;
+
+        // the layout is gone, so the account is closed by hand, the rent goes to the admin
+        let info = tags.to_account_info();
+        **user.try_borrow_mut_lamports()? += info.lamports();
+        **info.try_borrow_mut_lamports()? = 0;
+        info.assign(&system_program::ID);
+        info.realloc(0, false)?;
+        Ok(())
+    }
+}
@@ -13,7 +13,7 @@ pub struct CreateBank<'info> {
 
 impl<'info> CreateBank<'_> {
     pub fn process(&mut self, bump: u8) -> Result<()> {
-        let Self { user, bank, .. } = self;
+        let Self { bank, .. } = self;
 
         // Pb is a PDA, so here we store the bump
         bank.size = 9900;
@@ -0,0 +1,50 @@
+use crate::*;
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(name: String)]
+pub struct CreateCollection<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // starts out empty, the account grows with every post that is added
+    #[account(init, payer = user, space = Collection::space(&name, 0), seeds = [b"collection", user_profile.uid.to_le_bytes().as_ref(), name.as_bytes()], bump)]
+    pub collection: Account<'info, Collection>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateCollection<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        name: String,
+        visibility: u8,
+        bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self {
+            user_profile,
+            collection,
+            ..
+        } = self;
+
+        check_name(&name)?;
+        check_visibility(visibility)?;
+
+        let clock: Clock = Clock::get().unwrap();
+        collection.ts = clock.unix_timestamp;
+
+        collection.uid = user_profile.uid;
+        collection.name = name;
+        collection.visibility = visibility;
+
+        // Collection is a PDA, so here we store the bump
+        collection.bump = bump;
+        Ok(())
+    }
+}
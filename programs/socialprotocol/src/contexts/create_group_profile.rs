@@ -36,7 +36,6 @@ impl<'info> CreateGroupProfile<'_> {
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
             ..
         } = self;
@@ -0,0 +1,31 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CreateInbox<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // retrieve user id and check if signer is the owner of this user id
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    // one inbox per user, seeded by the user id
+    #[account(init, payer = user, space = 8 + mem::size_of::<Inbox>(), seeds = [b"inbox", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub inbox: Box<Account<'info, Inbox>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateInbox<'_> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let Self {
+            user_profile,
+            inbox,
+            ..
+        } = self;
+
+        inbox.uid = user_profile.uid;
+
+        // Inbox is a PDA, so here we store the bump
+        inbox.bump = bump;
+        Ok(())
+    }
+}
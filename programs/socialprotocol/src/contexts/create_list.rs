@@ -0,0 +1,49 @@
+use crate::*;
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(name: String)]
+pub struct CreateList<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<List>() + name.len(), seeds = [b"list", user_profile.uid.to_le_bytes().as_ref(), name.as_bytes()], bump)]
+    pub list: Account<'info, List>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateList<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        name: String,
+        visibility: u8,
+        bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self {
+            user_profile, list, ..
+        } = self;
+
+        check_name(&name)?;
+        check_visibility(visibility)?;
+
+        let clock: Clock = Clock::get().unwrap();
+        list.ts = clock.unix_timestamp;
+
+        list.uid = user_profile.uid;
+        list.name = name;
+        list.visibility = visibility;
+        list.members = 0;
+        list.subscribers = 0;
+
+        // List is a PDA, so here we store the bump
+        list.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::*;
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(session: Pubkey)]
+pub struct CreateSessionKey<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // only a wallet of the profile can create session keys
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<SessionKey>(), seeds = [b"session_key", user_profile.uid.to_le_bytes().as_ref(), session.as_ref()], bump)]
+    pub session_key: Account<'info, SessionKey>,
+    // the session key pays for the rent and fees of its actions
+    #[account(mut, address = session)]
+    pub session_wallet: SystemAccount<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateSessionKey<'_> {
+    pub fn process(
+        &mut self,
+        session: Pubkey,
+        expiry: i64,
+        allowed: u32,
+        spend_limit: u64,
+        lamports: u64,
+        bump: u8,
+    ) -> Result<()> {
+        let Self {
+            user,
+            user_profile,
+            session_key,
+            session_wallet,
+            system_program,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            expiry > clock.unix_timestamp && expiry <= clock.unix_timestamp + MAX_SESSION_DURATION,
+            SplingError::InvalidSessionExpiry
+        );
+
+        session_key.uid = user_profile.uid;
+        session_key.owner = *user.key;
+        session_key.session = session;
+        session_key.expiry = expiry;
+        session_key.allowed = allowed;
+
+        // token payments also need the session to be a delegate of the token account
+        session_key.spend_limit = spend_limit;
+        session_key.spent = 0;
+
+        // SessionKey is a PDA, so here we store the bump
+        session_key.bump = bump;
+
+        if lamports > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: user.to_account_info(),
+                        to: session_wallet.to_account_info(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::*;
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(name: String)]
+pub struct CreateTag<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // retrieve spling account to assign the tag id
+    #[account(mut, seeds = [b"spling"], bump = spling.bump)]
+    pub spling: Account<'info, Spling>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // create new tag account, using the normalized tag name as seed
+    #[account(init, payer = user, space = 8 + mem::size_of::<Tag>() + MAX_TAG_LENGTH, seeds = [b"tag".as_ref(), name.as_bytes()], bump)]
+    pub tag: Account<'info, Tag>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTag<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        name: String,
+        bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_POST, None)?;
+
+        let Self {
+            spling,
+            user_profile,
+            tag,
+            ..
+        } = self;
+
+        // the seed has to be the normalized name, so every tag has one account
+        require!(Tag::normalize(&name)? == name, SplingError::InvalidTagName);
+
+        // load the clock to create a creation date timestamp (ts)
+        let clock: Clock = Clock::get().unwrap();
+        tag.ts = clock.unix_timestamp;
+
+        // the tag id is assigned once and never changes
+        tag.id = &spling.tags + 1;
+        spling.tags += 1;
+
+        tag.name = name;
+        tag.posts = 0;
+        tag.uid = user_profile.uid;
+
+        // Tag is a PDA, so here we store the bump
+        tag.bump = bump;
+        Ok(())
+    }
+}
@@ -8,21 +8,25 @@ pub struct CreateUserProfile<'info> {
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
     // create new user profile account, using the user id as seed
-    #[account(init, payer = user, space = 8 + mem::size_of::<UserProfile>(), seeds = [b"user_profile".as_ref(), user.key().as_ref()], bump)]
+    #[account(init, payer = user, space = 8 + mem::size_of::<UserProfile>(), seeds = [b"user_profile".as_ref(), (spling.users + 1).to_le_bytes().as_ref()], bump)]
     pub user_profile: Account<'info, UserProfile>,
+    // map the wallet to the user id, a wallet belongs to one profile
+    #[account(init, payer = user, space = 8 + mem::size_of::<Wallet>(), seeds = [b"wallet".as_ref(), user.key().as_ref()], bump)]
+    pub wallet: Account<'info, Wallet>,
     #[account(mut)]
     pub b: Account<'info, B>,
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> CreateUserProfile<'_> {
-    pub fn process(&mut self, shdw: Pubkey, amount: Option<u64>, bump: u8) -> Result<()> {
+impl<'info> CreateUserProfile<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>], shdw: Pubkey, _amount: Option<u64>, referrer: Option<u32>, bump: u8, wallet_bump: u8) -> Result<()> {
         let Self {
             user_profile,
+            wallet,
             spling,
             user,
-            b,
+            system_program,
             ..
         } = self;
 
@@ -46,6 +50,32 @@ impl<'info> CreateUserProfile<'_> {
         // UserProfile is a PDA, so here we store the bump
         user_profile.bump = bump;
 
+        wallet.uid = user_profile.uid;
+        wallet.wallet = *user.key;
+
+        // Wallet is a PDA, so here we store the bump
+        wallet.bump = wallet_bump;
+
+        // early users earn a badge, its account is passed in the remaining accounts
+        if user_profile.uid <= BADGE_EARLY_USERS {
+            Badge::award(remaining_accounts, user_profile.uid, BADGE_EARLY_USER, &user.to_account_info(), &system_program.to_account_info())?;
+        }
+
+        // the profile of the referrer is passed in the remaining accounts
+        // a wallet belongs to one profile, so the new wallet can't be one of the referrer's
+        if let Some(referrer) = referrer {
+            require!(referrer != user_profile.uid, SplingError::InvalidReferrer);
+            let info = find_account(remaining_accounts, &UserProfile::pubkey(referrer)).ok_or(SplingError::InvalidReferrer)?;
+            require!(!info.data_is_empty() && info.is_writable, SplingError::InvalidReferrer);
+
+            let mut referrer_profile: Account<UserProfile> = Account::try_from(info)?;
+            referrer_profile.referrals += 1;
+            referrer_profile.exit(&crate::ID)?;
+
+            user_profile.referrer = referrer;
+            emit!(UserReferred { uid: user_profile.uid, referrer, ts: user_profile.ts });
+        }
+
 
         Ok(())
     }
@@ -0,0 +1,45 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(follower: u32)]
+pub struct DeclineFollowRequest<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    // pending requests are declined, approved ones are revoked
+    #[account(mut, seeds = [b"follow_request", user_profile.uid.to_le_bytes().as_ref(), follower.to_le_bytes().as_ref()], bump = follow_request.bump, close = payer)]
+    pub follow_request: Account<'info, FollowRequest>,
+    // the wallet that paid the rent gets it back
+    #[account(mut, address = follow_request.user)]
+    /// CHECK: wallet recorded on the follow request
+    pub payer: UncheckedAccount<'info>,
+    // a revoked follower stops following, the profile may be deleted already
+    #[account(mut, address = UserProfile::pubkey(follow_request.follower))]
+    /// CHECK: profile of the requesting user, deserialized when not empty
+    pub follower_profile: UncheckedAccount<'info>,
+}
+
+impl<'info> DeclineFollowRequest<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            user_profile,
+            follow_request,
+            follower_profile,
+            ..
+        } = self;
+
+        if follow_request.status == FOLLOW_REQUEST_APPROVED && !follower_profile.data_is_empty() {
+            let mut profile: Account<UserProfile> = Account::try_from(follower_profile)?;
+            profile.following.retain(|x| *x != user_profile.uid);
+            profile.exit(&crate::ID)?;
+        }
+
+        let clock: Clock = Clock::get().unwrap();
+        emit!(FollowDeclined {
+            uid: user_profile.uid,
+            follower: follow_request.follower,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct DeleteCollection<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"collection", user_profile.uid.to_le_bytes().as_ref(), name.as_bytes()], bump = collection.bump, close = user)]
+    pub collection: Account<'info, Collection>,
+}
+
+impl<'info> DeleteCollection<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        // closing the collection account is all there is to it
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ pub struct DeleteGroupProfile<'info> {
     pub spling: Account<'info, Spling>,
     // create new group profile account, using the group id as seed
     // TODO: check if user = group
-    #[account(mut, seeds = [b"group_profile".as_ref(), user.key().as_ref()], bump = group_profile.bump, close = spling)]
+    #[account(mut, seeds = [b"group_profile".as_ref(), user.key().as_ref()], bump = group_profile.bump, close = user)]
     pub group_profile: Account<'info, GroupProfile>,
     #[account(mut)]
     pub b: Account<'info, B>,
@@ -28,13 +28,11 @@ pub struct DeleteGroupProfile<'info> {
 impl<'info> DeleteGroupProfile<'_> {
     pub fn process(&mut self, amount: Option<u64>) -> Result<()> {
         let Self {
-            spling,
             user,
             b,
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
             ..
         } = self;
@@ -0,0 +1,24 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct DeleteList<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // members have to be removed first, so no member accounts are left behind
+    #[account(mut, seeds = [b"list", user_profile.uid.to_le_bytes().as_ref(), name.as_bytes()], bump = list.bump, constraint = list.members == 0 @ SplingError::ListNotEmpty, close = user)]
+    pub list: Account<'info, List>,
+}
+
+impl<'info> DeleteList<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        // subscribers can still unsubscribe from a deleted list, to get their rent back
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use clockwork_sdk::{state::Thread, ThreadProgram};
 
 #[derive(Accounts)]
 #[instruction(group_id: u32, shdw: Pubkey)]
@@ -7,10 +8,21 @@ pub struct DeletePost<'info> {
     pub user: Signer<'info>,
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
-    #[account(seeds = [b"user_profile", user.key().as_ref()], bump = user_profile.bump, has_one = user)]
+    // the post no longer counts towards the live posts of the user
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
     pub user_profile: Account<'info, UserProfile>,
-    #[account(mut, seeds = [b"post".as_ref(), shdw.as_ref()], bump = post.bump, constraint = user_profile.uid == post.uid, close = spling)]
+    #[account(mut, seeds = [b"post".as_ref(), shdw.as_ref()], bump = post.bump, constraint = user_profile.uid == post.uid, close = payer)]
     pub post: Account<'info, Post>,
+    // likes of the post are closed together with the post
+    #[account(mut, seeds = [b"likes".as_ref(), post.key().as_ref()], bump = likes.bump, close = payer)]
+    pub likes: Account<'info, Likes>,
+    // tags of the post no longer count towards the trending leaderboard
+    #[account(mut, seeds = [b"trending"], bump = trending.bump)]
+    pub trending: Box<Account<'info, Trending>>,
+    // rent goes back to the account that paid for the post
+    #[account(mut, address = post.payer)]
+    /// CHECK: payer recorded on the post
+    pub payer: UncheckedAccount<'info>,
     #[account(mut)]
     pub b: Account<'info, B>,
     #[account(mut)]
@@ -23,25 +35,108 @@ pub struct DeletePost<'info> {
     #[account()]
     pub mint: Account<'info, Mint>,
     pub token_program: Program<'info, Token>,
+    // the expiry thread of the post is deleted together with the post
+    #[account(mut, address = Thread::pubkey(post.key(), "post_thread".to_string()))]
+    /// CHECK: thread account, only deleted when it exists
+    pub post_thread: UncheckedAccount<'info>,
+    // a scheduled post also has a publish thread
+    #[account(mut, address = Thread::pubkey(post.key(), "publish_thread".to_string()))]
+    /// CHECK: thread account, only deleted when it exists
+    pub publish_thread: UncheckedAccount<'info>,
+    // and a poll with a close timestamp has a poll thread
+    #[account(mut, address = Thread::pubkey(post.key(), "poll_thread".to_string()))]
+    /// CHECK: thread account, only deleted when it exists
+    pub poll_thread: UncheckedAccount<'info>,
+    #[account(address = ThreadProgram::id())]
+    /// CHECK: thread program, only invoked when the post has a thread
+    pub thread_program: UncheckedAccount<'info>,
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> DeletePost<'_> {
-    pub fn process(&mut self, amount: Option<u64>) -> Result<()> {
+impl<'info> DeletePost<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        shdw: Pubkey,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_DELETE, amount)?;
+
         let Self {
-            spling,
             user_profile,
+            post,
+            trending,
+            payer,
+            post_thread,
+            publish_thread,
+            poll_thread,
+            thread_program,
             user,
             b,
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
             ..
         } = self;
 
+        // tag accounts and their post index entries are passed in the remaining accounts
+        post.remove_tags(
+            post.key(),
+            trending,
+            remaining_accounts,
+            &payer.to_account_info(),
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        // expired and removed posts were taken off already
+        if post.is_live() {
+            user_profile.live_posts = user_profile.live_posts.saturating_sub(1);
+        }
+
+        // cancel the expiry thread, so it doesn't fire on a closed post
+        // and refund the thread balance to the payer
+        if !post_thread.data_is_empty() {
+            clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadDelete {
+                    authority: post.to_account_info(),
+                    close_to: payer.to_account_info(),
+                    thread: post_thread.to_account_info(),
+                },
+                &[&[b"post", shdw.as_ref(), &[post.bump]]],
+            ))?;
+        }
+
+        // same for the publish thread, the thread can't delete itself once it published the post
+        if !publish_thread.data_is_empty() {
+            clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadDelete {
+                    authority: post.to_account_info(),
+                    close_to: payer.to_account_info(),
+                    thread: publish_thread.to_account_info(),
+                },
+                &[&[b"post", shdw.as_ref(), &[post.bump]]],
+            ))?;
+        }
+
+        // the poll stays until it's swept, but nothing closes it once the post is gone
+        if !poll_thread.data_is_empty() {
+            clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadDelete {
+                    authority: post.to_account_info(),
+                    close_to: payer.to_account_info(),
+                    thread: poll_thread.to_account_info(),
+                },
+                &[&[b"post", shdw.as_ref(), &[post.bump]]],
+            ))?;
+        }
+
         match amount {
             None => (),
             Some(am) => {
@@ -7,10 +7,24 @@ pub struct DeleteReply<'info> {
     pub user: Signer<'info>,
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
-    #[account(seeds = [b"user_profile", user.key().as_ref()], bump = user_profile.bump, has_one = user)]
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
     pub user_profile: Account<'info, UserProfile>,
-    #[account(mut, seeds = [b"reply".as_ref(), shdw.as_ref()], bump = reply.bump, constraint = user_profile.uid == reply.uid, close = spling)]
+    #[account(mut, seeds = [b"reply".as_ref(), shdw.as_ref()], bump = reply.bump, constraint = user_profile.uid == reply.uid, close = payer)]
     pub reply: Account<'info, Reply>,
+    // rent goes back to the account that paid for the reply, the spling or bank when subsidized
+    #[account(mut, address = reply.payer)]
+    /// CHECK: payer recorded on the reply
+    pub payer: UncheckedAccount<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    // the reputation and reward points the reply earned the post author are taken back
+    #[account(mut, address = UserProfile::pubkey(reply.post_uid))]
+    /// CHECK: profile of the post author, may not exist
+    pub author_profile: UncheckedAccount<'info>,
+    // the creator points of the post author are passed in the remaining accounts
+    #[account(mut, address = RewardPool::pubkey())]
+    /// CHECK: reward pool, may not exist
+    pub reward_pool: UncheckedAccount<'info>,
     #[account(mut)]
     pub b: Account<'info, B>,
     #[account(mut)]
@@ -26,21 +40,42 @@ pub struct DeleteReply<'info> {
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 }
-impl<'info> DeleteReply<'_> {
-    pub fn process(&mut self, amount: Option<u64>) -> Result<()> {
+impl<'info> DeleteReply<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: Option<u64>,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_DELETE, amount)?;
+
         let Self {
-            spling,
-            user_profile,
+            reply,
+            config,
+            author_profile,
+            reward_pool,
             user,
             b,
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
             ..
         } = self;
 
+        // like an unlike, the reply no longer counts for the post author
+        if reply.post_uid != reply.uid {
+            UserProfile::add_reputation(author_profile, -config.reputation.reply)?;
+            RewardPool::take_back_points(
+                reward_pool,
+                remaining_accounts,
+                reply.post_uid,
+                REWARD_POINTS_REPLY,
+                reply.epoch,
+            )?;
+        }
+
         match amount {
             None => (),
             Some(am) => {
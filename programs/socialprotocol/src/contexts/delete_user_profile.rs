@@ -6,8 +6,15 @@ pub struct DeleteUserProfile<'info> {
     pub user: Signer<'info>,
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
-    #[account(mut, seeds = [b"user_profile".as_ref(), user.key().as_ref() ], bump = user_profile.bump, has_one = user, close = spling)]
+    // only the primary wallet can delete the profile, once the other wallets are removed
+    #[account(mut, seeds = [b"user_profile".as_ref(), user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, has_one = user, constraint = user_profile.wallets.iter().all(|w| *w == Pubkey::default()) @ SplingError::WalletsRemaining, close = user)]
     pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"wallet".as_ref(), user.key().as_ref()], bump = wallet.bump, close = user)]
+    pub wallet: Account<'info, Wallet>,
+    // staked tokens can only be withdrawn with the profile, so the position has to be empty
+    #[account(mut, address = StakePosition::pubkey(user_profile.uid))]
+    /// CHECK: stake position of the user, may not exist
+    pub stake_position: UncheckedAccount<'info>,
     #[account(mut)]
     pub b: Account<'info, B>,
     #[account(mut)]
@@ -27,18 +34,26 @@ pub struct DeleteUserProfile<'info> {
 impl<'info> DeleteUserProfile<'_> {
     pub fn process(&mut self, amount: Option<u64>) -> Result<()> {
         let Self {
-            spling,
-            user_profile,
+            stake_position,
             user,
             b,
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
             ..
         } = self;
 
+        // the empty position is closed with the profile, its rent goes back to the user
+        if !stake_position.data_is_empty() {
+            let position: Account<StakePosition> = Account::try_from(stake_position)?;
+            require!(
+                position.amount == 0 && position.unstaking == 0,
+                SplingError::StakeRemaining
+            );
+            position.close(user.to_account_info())?;
+        }
+
         match amount {
             None => (),
             Some(am) => {
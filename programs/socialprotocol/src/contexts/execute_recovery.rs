@@ -0,0 +1,66 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    // anyone can execute an approved recovery, once the delay has passed
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"guardians", user_profile.uid.to_le_bytes().as_ref()], bump = guardians.bump)]
+    pub guardians: Account<'info, Guardians>,
+    #[account(mut, seeds = [b"recovery", user_profile.uid.to_le_bytes().as_ref(), recovery.index.to_le_bytes().as_ref()], bump = recovery.bump, constraint = recovery.status == RECOVERY_PENDING @ SplingError::RecoveryNotPending)]
+    pub recovery: Account<'info, Recovery>,
+    // the lost primary wallet no longer maps to the profile
+    #[account(mut, seeds = [b"wallet", user_profile.user.as_ref()], bump = previous_wallet.bump, close = user)]
+    pub previous_wallet: Account<'info, Wallet>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<Wallet>(), seeds = [b"wallet", recovery.wallet.as_ref()], bump)]
+    pub wallet: Account<'info, Wallet>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteRecovery<'_> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let Self {
+            user_profile,
+            guardians,
+            recovery,
+            wallet,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            recovery.approved_at != 0
+                && clock.unix_timestamp >= recovery.approved_at + guardians.delay,
+            SplingError::RecoveryNotReady
+        );
+        require!(
+            !recovery.is_expired(guardians.delay, clock.unix_timestamp),
+            SplingError::RecoveryExpired
+        );
+
+        // only the primary wallet changes, the new primary can remove the other wallets
+        let previous = user_profile.user;
+        user_profile.user = recovery.wallet;
+
+        wallet.uid = user_profile.uid;
+        wallet.wallet = recovery.wallet;
+
+        // Wallet is a PDA, so here we store the bump
+        wallet.bump = bump;
+
+        recovery.status = RECOVERY_EXECUTED;
+        guardians.pending = false;
+
+        emit!(RecoveryExecuted {
+            uid: user_profile.uid,
+            recovery: recovery.key(),
+            previous,
+            primary: recovery.wallet,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -26,13 +26,11 @@ pub struct ExtractBank<'info> {
 impl<'info> ExtractBank<'_> {
     pub fn process(&mut self, amount: Option<u64>) -> Result<()> {
         let Self {
-            spling,
             user,
             b,
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
             ..
         } = self;
@@ -1,6 +1,7 @@
 use crate::*;
 
 #[derive(Accounts)]
+#[instruction(address: u32)]
 pub struct FollowUser<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -9,11 +10,17 @@ pub struct FollowUser<'info> {
     // increase user profile account size, with 4 (u32) to accomodate adding the user id to the user's follows
     #[account(
         mut, 
-        seeds = [b"user_profile", user.key().as_ref()],
-        bump = user_profile.bump,
-        has_one = user
+        seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()],
+        bump = user_profile.bump
     )]
     pub user_profile: Account<'info, UserProfile>,
+    // settings of the followed user, a protected account gets a follow request instead
+    #[account(address = ProfileSettings::pubkey(address))]
+    /// CHECK: settings of the followed user, may not exist
+    pub target_settings: UncheckedAccount<'info>,
+    #[account(mut, address = FollowRequest::pubkey(address, user_profile.uid))]
+    /// CHECK: follow request, only created when the followed user is protected
+    pub follow_request: UncheckedAccount<'info>,
     #[account(mut)]
     pub b: Account<'info, B>,
     #[account(mut)]
@@ -30,12 +37,56 @@ pub struct FollowUser<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> FollowUser<'_> {
-    pub fn process(&mut self,address:u32, amount:Option<u64>) -> Result<()> {
-        let Self {spling,user_profile,user,b,receiver,sender_token_account,receiver_token_account,mint,token_program,..} = self;
+impl<'info> FollowUser<'info> {
+    pub fn process(&mut self,remaining_accounts:&[AccountInfo<'info>],address:u32, amount:Option<u64>) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_FOLLOW, amount)?;
 
-        // add user id to vector
-        user_profile.following.push(address);
+        let Self {user_profile,target_settings,follow_request,user,b,receiver,sender_token_account,receiver_token_account,token_program,system_program,..} = self;
+
+        let clock: Clock = Clock::get().unwrap();
+
+        if ProfileSettings::is_protected(target_settings)? {
+            // a protected account approves its followers, see approve_follow_request
+            let target = address.to_le_bytes();
+            let follower = user_profile.uid.to_le_bytes();
+            let (_, bump) = Pubkey::find_program_address(&[b"follow_request", &target, &follower], &crate::ID);
+            create_account(
+                &user.to_account_info(),
+                follow_request,
+                &system_program.to_account_info(),
+                8 + mem::size_of::<FollowRequest>(),
+                &[b"follow_request", &target, &follower, &[bump]],
+            )?;
+            let mut data = follow_request.try_borrow_mut_data()?;
+            FollowRequest {
+                uid: address,
+                follower: user_profile.uid,
+                user: user.key(),
+                status: FOLLOW_REQUEST_PENDING,
+                ts: clock.unix_timestamp,
+                bump,
+            }
+            .try_serialize(&mut &mut data[..])?;
+
+            emit!(FollowRequested {
+                uid: address,
+                follower: user_profile.uid,
+                ts: clock.unix_timestamp,
+            });
+        } else {
+            // add user id to vector
+            user_profile.following.push(address);
+
+            // the inbox of the followed user is passed in the remaining accounts
+            Inbox::notify(remaining_accounts, address, Notification {
+                kind: NOTIFICATION_FOLLOW,
+                uid: user_profile.uid,
+                key: Pubkey::default(),
+                ts: clock.unix_timestamp,
+            })?;
+        }
 
 
         match amount {
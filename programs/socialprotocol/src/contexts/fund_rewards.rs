@@ -0,0 +1,37 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut, seeds = [b"reward_vault"], bump)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub sender_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FundRewards<'_> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        let Self {
+            user,
+            reward_vault,
+            sender_token_account,
+            token_program,
+            ..
+        } = self;
+
+        // transfer Spling tokens
+        let cpi_context = CpiContext::new(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: sender_token_account.to_account_info(),
+                to: reward_vault.to_account_info(),
+                authority: user.to_account_info(),
+            },
+        );
+
+        token::transfer(cpi_context, amount)
+    }
+}
@@ -0,0 +1,20 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct GetTrendingTags<'info> {
+    #[account(seeds = [b"trending"], bump = trending.bump)]
+    pub trending: Account<'info, Trending>,
+}
+
+impl<'info> GetTrendingTags<'_> {
+    pub fn process(&self, limit: u8) -> Result<Vec<TrendingTag>> {
+        let Self { trending } = self;
+
+        // scores are stored as of their last update, so decay them to today
+        let clock: Clock = Clock::get().unwrap();
+        let mut tags = trending.top(Tag::day(clock.unix_timestamp));
+        tags.truncate(limit as usize);
+
+        Ok(tags)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(guardian: u8, wallet: Pubkey)]
+pub struct InitiateRecovery<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", guardians.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"guardians", guardians.uid.to_le_bytes().as_ref()], bump = guardians.bump)]
+    pub guardians: Account<'info, Guardians>,
+    // every recovery gets a new account, so earlier approvals never count again
+    #[account(init, payer = user, space = 8 + mem::size_of::<Recovery>(), seeds = [b"recovery", guardians.uid.to_le_bytes().as_ref(), guardians.recoveries.to_le_bytes().as_ref()], bump)]
+    pub recovery: Account<'info, Recovery>,
+    // the new wallet can't belong to another profile, or the recovery could never execute
+    #[account(seeds = [b"wallet", wallet.as_ref()], bump, constraint = recovery_wallet.data_is_empty() @ SplingError::InvalidRecoveryWallet)]
+    /// CHECK: wallet mapping of the new wallet, has to be empty
+    pub recovery_wallet: UncheckedAccount<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitiateRecovery<'info> {
+    // any guardian can initiate a recovery, user guardians pass their profile in the remaining accounts
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        guardian: u8,
+        wallet: Pubkey,
+        bump: u8,
+    ) -> Result<()> {
+        let Self {
+            user,
+            user_profile,
+            guardians,
+            recovery,
+            ..
+        } = self;
+
+        require!(!guardians.pending, SplingError::RecoveryPending);
+        guardians
+            .guardians
+            .get(guardian as usize)
+            .ok_or(SplingError::NotAGuardian)?
+            .check_signer(user.key, &guardians.guardians, remaining_accounts)?;
+        require!(
+            wallet != Pubkey::default() && !user_profile.is_wallet(&wallet),
+            SplingError::InvalidRecoveryWallet
+        );
+
+        let clock: Clock = Clock::get().unwrap();
+
+        recovery.uid = guardians.uid;
+        recovery.index = guardians.recoveries;
+        recovery.wallet = wallet;
+        recovery.initiator = user.key();
+        recovery.approvals = 0;
+        recovery.status = RECOVERY_PENDING;
+        recovery.ts = clock.unix_timestamp;
+        recovery.approved_at = 0;
+
+        // Recovery is a PDA, so here we store the bump
+        recovery.bump = bump;
+
+        guardians.recoveries += 1;
+        guardians.pending = true;
+
+        emit!(RecoveryInitiated {
+            uid: recovery.uid,
+            recovery: recovery.key(),
+            wallet,
+            initiator: user.key(),
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(uid: u32, kind: u8)]
+pub struct IssueAttestation<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"issuer", user.key().as_ref()], bump = issuer.bump, constraint = issuer.allows(kind) @ SplingError::IssuerNotAllowed)]
+    pub issuer: Account<'info, Issuer>,
+    // only existing users can be attested
+    #[account(seeds = [b"user_profile", uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // created on first use, renewed or replaced afterwards
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<Attestation>(), seeds = [b"attestation", uid.to_le_bytes().as_ref(), kind.to_le_bytes().as_ref()], bump)]
+    pub attestation: Account<'info, Attestation>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> IssueAttestation<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        uid: u32,
+        kind: u8,
+        expiry: i64,
+        bump: u8,
+    ) -> Result<()> {
+        let Self {
+            user, attestation, ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            expiry == 0 || expiry > clock.unix_timestamp,
+            SplingError::InvalidAttestationExpiry
+        );
+
+        // issuing it again would undo the revocation of the admin
+        require!(
+            !attestation.admin_revoked,
+            SplingError::AttestationRevokedByAdmin
+        );
+
+        // another issuer can only take over once the attestation is revoked, expired or
+        // its issuer removed, the issuer account of the attestation is passed in the
+        // remaining accounts
+        if attestation.issuer != Pubkey::default() && attestation.issuer != user.key() {
+            let previous = find_account(remaining_accounts, &Issuer::pubkey(attestation.issuer))
+                .ok_or(SplingError::AttestationExists)?;
+            require!(
+                !attestation.is_valid(previous, clock.unix_timestamp)?,
+                SplingError::AttestationExists
+            );
+        }
+
+        attestation.uid = uid;
+        attestation.kind = kind;
+        attestation.issuer = user.key();
+        attestation.expiry = expiry;
+        attestation.revoked = false;
+        attestation.ts = clock.unix_timestamp;
+
+        // Attestation is a PDA, so here we store the bump
+        attestation.bump = bump;
+
+        emit!(AttestationIssued {
+            uid,
+            kind,
+            issuer: user.key(),
+            expiry,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
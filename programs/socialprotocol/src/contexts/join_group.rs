@@ -1,5 +1,6 @@
 use crate::*;
 #[derive(Accounts)]
+#[instruction(address: u32)]
 pub struct JoinGroup<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -8,10 +9,19 @@ pub struct JoinGroup<'info> {
     // increase user profile account size, with 4 (u32) to accomodate adding the group id to the user's joined groups
     #[account(
         mut, 
-        seeds = [b"user_profile", user.key().as_ref()],
-        bump = user_profile.bump,
-        has_one = user)]
+        seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()],
+        bump = user_profile.bump)]
     pub user_profile: Account<'info, UserProfile>,
+    // groups can be closed to users below a reputation threshold
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    // the join order of the group decides its founding members
+    #[account(mut, seeds = [b"group_profile", group_profile.group.as_ref()], bump = group_profile.bump, constraint = group_profile.gid == address @ SplingError::InvalidGroup)]
+    pub group_profile: Account<'info, GroupProfile>,
+    // founding members earn a badge
+    #[account(mut, address = Badge::pubkey(user_profile.uid, BADGE_GROUP_FOUNDER))]
+    /// CHECK: badge of the user, created when earned
+    pub founder_badge: UncheckedAccount<'info>,
     #[account(mut)]
     pub b: Account<'info, B>,
     #[account(mut)]
@@ -28,13 +38,28 @@ pub struct JoinGroup<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> JoinGroup<'_> {
-    pub fn process(&mut self,address:u32,amount:Option<u64>) -> Result<()> {
-        let Self {spling,user_profile,user,b,receiver,sender_token_account,receiver_token_account,mint,token_program,..} = self;
+impl<'info> JoinGroup<'info> {
+    pub fn process(&mut self,remaining_accounts:&[AccountInfo<'info>],address:u32,amount:Option<u64>) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_GROUP, amount)?;
+
+        let Self {user_profile,config,group_profile,founder_badge,user,b,receiver,sender_token_account,receiver_token_account,token_program,system_program,..} = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            config.reputation.can_join_group(user_profile, clock.unix_timestamp),
+            SplingError::ReputationTooLow
+        );
 
         // add group id to vector
         user_profile.groups.push(address);
 
+        group_profile.joined = group_profile.joined.saturating_add(1);
+        if Badge::is_founding_member(group_profile.joined) {
+            Badge::award(&[founder_badge.to_account_info()], user_profile.uid, BADGE_GROUP_FOUNDER, &user.to_account_info(), &system_program.to_account_info())?;
+        }
+
         match amount {
             None => (),
             Some(am) => {
@@ -6,7 +6,7 @@ pub struct LeaveGroup<'info> {
     pub user: Signer<'info>,
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
-    #[account(mut, seeds = [b"user_profile", user.key().as_ref()], has_one = user, bump = user_profile.bump)]
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
     pub user_profile: Account<'info, UserProfile>,
     #[account(mut)]
     pub b: Account<'info, B>,
@@ -24,17 +24,24 @@ pub struct LeaveGroup<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> LeaveGroup<'_> {
-    pub fn process(&mut self, address: u32, amount: Option<u64>) -> Result<()> {
+impl<'info> LeaveGroup<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        address: u32,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_GROUP, amount)?;
+
         let Self {
-            spling,
             user_profile,
             user,
             b,
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
             ..
         } = self;
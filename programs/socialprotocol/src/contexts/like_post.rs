@@ -7,13 +7,34 @@ pub struct LikePost<'info> {
     pub user: Signer<'info>,
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
-    #[account(seeds = [b"user_profile", user.key().as_ref()], bump = user_profile.bump, has_one = user)]
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
     pub user_profile: Account<'info, UserProfile>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    // per user rate state, created on the first rate limited action
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<RateLimit>(), seeds = [b"rate_limit", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub rate_limit: Box<Account<'info, RateLimit>>,
     // get a post account, no way to add constraint because shdw (hash) is unknown
-    #[account()]
+    #[account(constraint = post.st == POST_STATUS_ACTIVE @ SplingError::PostNotPublished)]
     pub post: Account<'info, Post>,
     #[account(mut, seeds = [b"likes".as_ref(), post.key().as_ref()], bump = likes.bump)]
     pub likes: Account<'info, Likes>,
+    // settings of the author and the follow request of this user, for protected accounts
+    #[account(address = ProfileSettings::pubkey(post.uid))]
+    /// CHECK: settings of the author, may not exist
+    pub author_settings: UncheckedAccount<'info>,
+    #[account(address = FollowRequest::pubkey(post.uid, user_profile.uid))]
+    /// CHECK: follow request of this user, may not exist
+    pub follow_request: UncheckedAccount<'info>,
+    // the author earns reputation, unless the profile is deleted
+    #[account(mut, address = UserProfile::pubkey(post.uid))]
+    /// CHECK: profile of the author, may not exist
+    pub author_profile: UncheckedAccount<'info>,
+    // the author earns reward points in the current epoch, once rewards are set up
+    // the creator points of the author are passed in the remaining accounts
+    #[account(mut, address = RewardPool::pubkey())]
+    /// CHECK: reward pool, may not exist
+    pub reward_pool: UncheckedAccount<'info>,
     #[account(mut)]
     pub b: Account<'info, B>,
     #[account(mut)]
@@ -30,33 +51,131 @@ pub struct LikePost<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> LikePost<'_> {
-    pub fn process(&mut self, amount: Option<u64>) -> Result<()> {
+impl<'info> LikePost<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: Option<u64>,
+        rate_limit_bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_LIKE, amount)?;
+
         let Self {
-            spling,
+            config,
+            rate_limit,
             user_profile,
             user,
             b,
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
+            post,
             likes,
+            author_settings,
+            follow_request,
+            author_profile,
+            reward_pool,
+            system_program,
             ..
         } = self;
 
+        // only approved followers can like posts of a protected account
+        FollowRequest::check_access(post.uid, user_profile.uid, author_settings, follow_request)?;
+
+        // and only subscribers can like posts for subscribers
+        let clock: Clock = Clock::get().unwrap();
+        post.check_subscriber(remaining_accounts, user_profile.uid, clock.unix_timestamp)?;
+
+        // stakers pass their stake position in the remaining accounts for the perks
+        let staked = StakePosition::staked(remaining_accounts, user_profile.uid)?;
+
+        // enforce the like rate limit of this user, unlikes count as well
+        rate_limit.uid = user_profile.uid;
+        rate_limit.bump = rate_limit_bump;
+        rate_limit.likes.record(
+            &config.limit_for(
+                &config.like_limit,
+                user_profile,
+                staked,
+                clock.unix_timestamp,
+            ),
+            clock.unix_timestamp,
+        )?;
+
         // check if user liked the post already
         if likes.users.contains(&user_profile.uid) {
-            // retain all user id's except for the user id to be deleted
-            likes.users.retain(|x| *x != user_profile.uid);
-            // increment like counter lower
-            likes.counter -= 1;
+            // remove the user id, along with the reward epoch of the like
+            let epoch = likes.remove(user_profile.uid);
+
+            // an unlike takes back the like, its reputation and its reward points
+            if post.uid != user_profile.uid {
+                UserProfile::update(author_profile, |author| {
+                    author.likes = author.likes.saturating_sub(1);
+                    if author.referrer == user_profile.uid {
+                        author.referrer_likes = author.referrer_likes.saturating_sub(1);
+                    }
+                    author.reputation = author.reputation.saturating_sub(config.reputation.like);
+                })?;
+                RewardPool::take_back_points(
+                    reward_pool,
+                    remaining_accounts,
+                    post.uid,
+                    REWARD_POINTS_LIKE,
+                    epoch,
+                )?;
+            }
         } else {
-            // add user id to vector
-            likes.users.push(user_profile.uid);
-            // increment like counter higher
-            likes.counter += 1;
+            // liking your own post earns nothing, and doesn't count towards badges
+            // likes of the referrer are tracked, as they don't count towards the referral
+            let mut epoch = NO_REWARD_EPOCH;
+            if post.uid != user_profile.uid {
+                let mut received = 0;
+                UserProfile::update(author_profile, |author| {
+                    author.likes = author.likes.saturating_add(1);
+                    received = author.likes;
+                    if author.referrer == user_profile.uid {
+                        author.referrer_likes = author.referrer_likes.saturating_add(1);
+                    }
+                    author.reputation = author.reputation.saturating_add(config.reputation.like);
+                })?;
+                epoch = RewardPool::accrue_points(
+                    reward_pool,
+                    remaining_accounts,
+                    post.uid,
+                    REWARD_POINTS_LIKE,
+                    &user.to_account_info(),
+                    &system_program.to_account_info(),
+                )?;
+
+                // the badge of the author is passed in the remaining accounts
+                if received == BADGE_LIKES {
+                    Badge::award(
+                        remaining_accounts,
+                        post.uid,
+                        BADGE_HUNDRED_LIKES,
+                        &user.to_account_info(),
+                        &system_program.to_account_info(),
+                    )?;
+                }
+            }
+
+            // add user id to vector, along with the reward epoch of the like
+            likes.add(user_profile.uid, epoch);
+
+            // the inbox of the author is passed in the remaining accounts
+            Inbox::notify(
+                remaining_accounts,
+                post.uid,
+                Notification {
+                    kind: NOTIFICATION_LIKE,
+                    uid: user_profile.uid,
+                    key: post.key(),
+                    ts: clock.unix_timestamp,
+                },
+            )?;
         }
 
         match amount {
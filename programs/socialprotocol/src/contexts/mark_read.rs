@@ -0,0 +1,26 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct MarkRead<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"inbox", user_profile.uid.to_le_bytes().as_ref()], bump = inbox.bump)]
+    pub inbox: Box<Account<'info, Inbox>>,
+}
+
+impl<'info> MarkRead<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>], count: u32) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_INBOX, None)?;
+
+        let Self { inbox, .. } = self;
+
+        // count is the number of notifications the client has seen,
+        // so notifications that arrived in the meantime stay unread
+        require!(count <= inbox.count, SplingError::InvalidReadCursor);
+        inbox.read = inbox.read.max(count);
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    // anyone can migrate an account, the signer pays the rent the account grows by
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // the layout is told apart by the discriminator and the size of the account
+    #[account(mut, owner = crate::ID)]
+    /// CHECK: program account in an earlier layout, deserialized below
+    pub account: UncheckedAccount<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateAccount<'info> {
+    // profiles are moved by migrate_user_profile, as their address changed too
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let Self {
+            user,
+            account,
+            system_program,
+        } = self;
+
+        let info = account.to_account_info();
+        let payer = user.to_account_info();
+        let system_program = system_program.to_account_info();
+
+        let discriminator: [u8; 8] = info
+            .try_borrow_data()?
+            .get(..8)
+            .and_then(|data| data.try_into().ok())
+            .ok_or(SplingError::NothingToMigrate)?;
+        let len = info.data_len();
+
+        if discriminator == Spling::discriminator() && len == 8 + mem::size_of::<LegacySpling>() {
+            let legacy: LegacySpling = load_legacy(&info, discriminator)?;
+            return write_migrated(
+                &info,
+                &payer,
+                &system_program,
+                8 + mem::size_of::<Spling>(),
+                &legacy.migrate(),
+            );
+        }
+
+        if discriminator == Post::discriminator() && len == 8 + mem::size_of::<LegacyPost>() {
+            let legacy: LegacyPost = load_legacy(&info, discriminator)?;
+
+            // the rent is refunded to the author, the migrated profile is passed in the remaining accounts
+            let author = Self::author(remaining_accounts, legacy.uid)?;
+            return write_migrated(
+                &info,
+                &payer,
+                &system_program,
+                8 + mem::size_of::<Post>(),
+                &legacy.migrate(author.user),
+            );
+        }
+
+        if discriminator == Reply::discriminator() && len == 8 + mem::size_of::<LegacyReply>() {
+            let legacy: LegacyReply = load_legacy(&info, discriminator)?;
+            let author = Self::author(remaining_accounts, legacy.uid)?;
+
+            // the post of the reply is passed in the remaining accounts, found by its post id
+            let post = remaining_accounts
+                .iter()
+                .filter(|info| info.owner == &crate::ID)
+                .find_map(|info| {
+                    Account::<Post>::try_from(info)
+                        .ok()
+                        .filter(|post| post.pid == legacy.pid)
+                })
+                .ok_or(SplingError::MigrationAccountMissing)?;
+
+            return write_migrated(
+                &info,
+                &payer,
+                &system_program,
+                8 + mem::size_of::<Reply>(),
+                &legacy.migrate(post.key(), post.uid, author.user),
+            );
+        }
+
+        if discriminator == Likes::discriminator() && len == 8 + mem::size_of::<LegacyLikes>() {
+            let legacy: LegacyLikes = load_legacy(&info, discriminator)?;
+            return write_migrated(
+                &info,
+                &payer,
+                &system_program,
+                8 + mem::size_of::<Likes>(),
+                &legacy.migrate(),
+            );
+        }
+
+        err!(SplingError::NothingToMigrate)
+    }
+
+    fn author(accounts: &[AccountInfo<'info>], uid: u32) -> Result<Account<'info, UserProfile>> {
+        let info = find_account(accounts, &UserProfile::pubkey(uid))
+            .ok_or(SplingError::MigrationAccountMissing)?;
+        Account::try_from(info)
+    }
+}
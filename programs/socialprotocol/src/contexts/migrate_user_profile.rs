@@ -0,0 +1,65 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(uid: u32)]
+pub struct MigrateUserProfile<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // profiles used to be seeded by the wallet, the old account is closed here
+    #[account(mut, seeds = [b"user_profile".as_ref(), user.key().as_ref()], bump, owner = crate::ID)]
+    /// CHECK: profile in the layout before profiles were seeded by user id, deserialized below
+    pub legacy_profile: UncheckedAccount<'info>,
+    // the same profile, seeded by its user id
+    #[account(init, payer = user, space = 8 + mem::size_of::<UserProfile>(), seeds = [b"user_profile".as_ref(), uid.to_le_bytes().as_ref()], bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // map the wallet to the user id, like a new profile
+    #[account(init, payer = user, space = 8 + mem::size_of::<Wallet>(), seeds = [b"wallet".as_ref(), user.key().as_ref()], bump)]
+    pub wallet: Account<'info, Wallet>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUserProfile<'_> {
+    pub fn process(&mut self, uid: u32, bump: u8, wallet_bump: u8) -> Result<()> {
+        let Self {
+            user,
+            legacy_profile,
+            user_profile,
+            wallet,
+            ..
+        } = self;
+
+        let legacy = LegacyUserProfile::load(legacy_profile)?;
+        require!(
+            legacy.uid == uid && legacy.user == *user.key,
+            SplingError::NotAuthorized
+        );
+
+        // the fields added since start out empty, like on a new profile
+        user_profile.ts = legacy.ts;
+        user_profile.user = legacy.user;
+        user_profile.uid = legacy.uid;
+        user_profile.st = legacy.st;
+        user_profile.shdw = legacy.shdw;
+        user_profile.groups = legacy.groups;
+        user_profile.following = legacy.following;
+
+        // UserProfile is a PDA, so here we store the bump
+        user_profile.bump = bump;
+
+        wallet.uid = uid;
+        wallet.wallet = *user.key;
+
+        // Wallet is a PDA, so here we store the bump
+        wallet.bump = wallet_bump;
+
+        // the rent of the old profile goes back to the user
+        let lamports = legacy_profile.lamports();
+        **legacy_profile.try_borrow_mut_lamports()? = 0;
+        **user.try_borrow_mut_lamports()? += lamports;
+        legacy_profile.assign(&system_program::ID);
+        legacy_profile.realloc(0, false)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(kind: u8)]
+pub struct MintBadge<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"badge", user_profile.uid.to_le_bytes().as_ref(), kind.to_le_bytes().as_ref()], bump = badge.bump, constraint = !badge.minted @ SplingError::BadgeAlreadyMinted)]
+    pub badge: Account<'info, Badge>,
+    // one mint per badge kind, created by the first user that mints the badge
+    #[account(init_if_needed, payer = user, seeds = [b"badge_mint", kind.to_le_bytes().as_ref()], bump, mint::decimals = 0, mint::authority = badge_mint, mint::freeze_authority = badge_mint)]
+    pub badge_mint: Account<'info, Mint>,
+    #[account(init, payer = user, seeds = [b"badge_token", badge.key().as_ref()], bump, token::mint = badge_mint, token::authority = user)]
+    pub token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> MintBadge<'_> {
+    // the token is frozen after minting, so it can't be transferred
+    pub fn process(&mut self, kind: u8, mint_bump: u8) -> Result<()> {
+        let Self {
+            badge,
+            badge_mint,
+            token_account,
+            token_program,
+            ..
+        } = self;
+
+        let kind_seed = kind.to_le_bytes();
+        let seeds: &[&[u8]] = &[b"badge_mint", kind_seed.as_ref(), &[mint_bump]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token::MintTo {
+                    mint: badge_mint.to_account_info(),
+                    to: token_account.to_account_info(),
+                    authority: badge_mint.to_account_info(),
+                },
+                &[seeds],
+            ),
+            1,
+        )?;
+
+        token::freeze_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::FreezeAccount {
+                account: token_account.to_account_info(),
+                mint: badge_mint.to_account_info(),
+                authority: badge_mint.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+
+        badge.minted = true;
+        Ok(())
+    }
+}
@@ -1,41 +1,185 @@
+pub mod add_to_collection;
+pub mod add_to_list;
+pub mod add_wallet;
+pub mod advance_epoch;
+pub mod approve_follow_request;
+pub mod approve_recovery;
+pub mod award_badge;
+pub mod block_user;
+pub mod bookmark_post;
+pub mod cancel_recovery;
+pub mod cancel_subscription;
+pub mod claim_referral_reward;
+pub mod claim_rewards;
 pub mod clockwork;
+pub mod close_attestation;
+pub mod close_post_thread;
+pub mod close_tags;
 pub mod create_b;
 pub mod create_bank;
+pub mod create_collection;
 pub mod create_group_profile;
+pub mod create_inbox;
+pub mod create_list;
+pub mod create_session_key;
+pub mod create_tag;
 pub mod create_user_profile;
+pub mod decline_follow_request;
+pub mod delete_collection;
 pub mod delete_group_profile;
+pub mod delete_list;
 pub mod delete_post;
 pub mod delete_reply;
 pub mod delete_user_profile;
+pub mod execute_recovery;
 pub mod extract_bank;
 pub mod follow_user;
+pub mod fund_rewards;
+pub mod get_trending_tags;
+pub mod initiate_recovery;
+pub mod issue_attestation;
 pub mod join_group;
 pub mod leave_group;
 pub mod like_post;
+pub mod mark_read;
+pub mod migrate_account;
+pub mod migrate_user_profile;
+pub mod mint_badge;
+pub mod moderate_post;
+pub mod open_conversation;
+pub mod remove_bookmark;
+pub mod remove_from_collection;
+pub mod remove_from_list;
+pub mod remove_issuer;
+pub mod remove_wallet;
+pub mod reorder_collection;
 pub mod reset_bank;
+pub mod revoke_attestation;
+pub mod revoke_session_key;
+pub mod rotate_primary_wallet;
+pub mod schedule_post;
+pub mod send_message;
+pub mod set_emission_rate;
+pub mod set_guardians;
+pub mod set_issuer;
+pub mod set_messaging_key;
+pub mod set_protected;
+pub mod set_subscribers_only;
+pub mod set_subscription_tier;
+pub mod setup_config;
+pub mod setup_referral_vault;
+pub mod setup_rewards;
 pub mod setup_spling;
-pub mod setup_tags;
+pub mod setup_stake_vault;
+pub mod setup_trending;
+pub mod stake;
+pub mod submit_poll;
 pub mod submit_post;
 pub mod submit_reply;
+pub mod subscribe;
+pub mod subscribe_list;
+pub mod sweep_orphaned_poll;
+pub mod sweep_orphaned_reply;
+pub mod unblock_user;
 pub mod unfollow_user;
+pub mod unstake;
+pub mod unsubscribe_list;
+pub mod update_config;
+pub mod update_referral;
+pub mod update_reputation;
+pub mod update_staking;
+pub mod vote_poll;
+pub mod withdraw_stake;
 
+pub use add_to_collection::*;
+pub use add_to_list::*;
+pub use add_wallet::*;
+pub use advance_epoch::*;
+pub use approve_follow_request::*;
+pub use approve_recovery::*;
+pub use award_badge::*;
+pub use block_user::*;
+pub use bookmark_post::*;
+pub use cancel_recovery::*;
+pub use cancel_subscription::*;
+pub use claim_referral_reward::*;
+pub use claim_rewards::*;
 pub use clockwork::*;
+pub use close_attestation::*;
+pub use close_post_thread::*;
+pub use close_tags::*;
 pub use create_b::*;
 pub use create_bank::*;
+pub use create_collection::*;
 pub use create_group_profile::*;
+pub use create_inbox::*;
+pub use create_list::*;
+pub use create_session_key::*;
+pub use create_tag::*;
 pub use create_user_profile::*;
+pub use decline_follow_request::*;
+pub use delete_collection::*;
 pub use delete_group_profile::*;
+pub use delete_list::*;
 pub use delete_post::*;
 pub use delete_reply::*;
 pub use delete_user_profile::*;
+pub use execute_recovery::*;
 pub use extract_bank::*;
 pub use follow_user::*;
+pub use fund_rewards::*;
+pub use get_trending_tags::*;
+pub use initiate_recovery::*;
+pub use issue_attestation::*;
 pub use join_group::*;
 pub use leave_group::*;
 pub use like_post::*;
+pub use mark_read::*;
+pub use migrate_account::*;
+pub use migrate_user_profile::*;
+pub use mint_badge::*;
+pub use moderate_post::*;
+pub use open_conversation::*;
+pub use remove_bookmark::*;
+pub use remove_from_collection::*;
+pub use remove_from_list::*;
+pub use remove_issuer::*;
+pub use remove_wallet::*;
+pub use reorder_collection::*;
 pub use reset_bank::*;
+pub use revoke_attestation::*;
+pub use revoke_session_key::*;
+pub use rotate_primary_wallet::*;
+pub use schedule_post::*;
+pub use send_message::*;
+pub use set_emission_rate::*;
+pub use set_guardians::*;
+pub use set_issuer::*;
+pub use set_messaging_key::*;
+pub use set_protected::*;
+pub use set_subscribers_only::*;
+pub use set_subscription_tier::*;
+pub use setup_config::*;
+pub use setup_referral_vault::*;
+pub use setup_rewards::*;
 pub use setup_spling::*;
-pub use setup_tags::*;
+pub use setup_stake_vault::*;
+pub use setup_trending::*;
+pub use stake::*;
+pub use submit_poll::*;
 pub use submit_post::*;
 pub use submit_reply::*;
+pub use subscribe::*;
+pub use subscribe_list::*;
+pub use sweep_orphaned_poll::*;
+pub use sweep_orphaned_reply::*;
+pub use unblock_user::*;
 pub use unfollow_user::*;
+pub use unstake::*;
+pub use unsubscribe_list::*;
+pub use update_config::*;
+pub use update_referral::*;
+pub use update_reputation::*;
+pub use update_staking::*;
+pub use vote_poll::*;
+pub use withdraw_stake::*;
@@ -0,0 +1,62 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ModeratePost<'info> {
+    pub user: Signer<'info>,
+    // only the protocol admin moderates posts
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    // tags of the post no longer count towards the trending leaderboard while it is removed
+    #[account(mut, seeds = [b"trending"], bump = trending.bump)]
+    pub trending: Box<Account<'info, Trending>>,
+    #[account(mut, address = UserProfile::pubkey(post.uid))]
+    /// CHECK: profile of the author, may not exist
+    pub author_profile: UncheckedAccount<'info>,
+}
+
+impl<'info> ModeratePost<'_> {
+    // remove a post and take reputation from the author, or restore it after an appeal
+    // the tag accounts of the post are passed in the remaining accounts
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo], remove: bool) -> Result<()> {
+        let Self {
+            config,
+            post,
+            trending,
+            author_profile,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+
+        // removed posts can't be liked, replied to or collected
+        if remove {
+            require!(post.st == POST_STATUS_ACTIVE, SplingError::PostNotPublished);
+            post.debit_tags(trending, remaining_accounts, clock.unix_timestamp)?;
+            post.st = POST_STATUS_REMOVED;
+            UserProfile::add_reputation(author_profile, -config.reputation.moderation)?;
+            UserProfile::update(author_profile, |author| {
+                author.live_posts = author.live_posts.saturating_sub(1)
+            })?;
+        } else {
+            // the current weight is given back, which differs if it changed in between
+            require!(post.st == POST_STATUS_REMOVED, SplingError::PostNotRemoved);
+            post.st = POST_STATUS_ACTIVE;
+            post.restore_tags(trending, remaining_accounts, clock.unix_timestamp)?;
+            UserProfile::add_reputation(author_profile, config.reputation.moderation)?;
+            UserProfile::update(author_profile, |author| {
+                author.live_posts = author.live_posts.saturating_add(1)
+            })?;
+        }
+
+        emit!(PostModerated {
+            post: post.key(),
+            pid: post.pid,
+            uid: post.uid,
+            removed: remove,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::*;
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(address: u32)]
+pub struct OpenConversation<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // both users need a messaging key, to encrypt messages to each other
+    #[account(seeds = [b"messaging_key", user_profile.uid.to_le_bytes().as_ref()], bump = messaging_key.bump)]
+    pub messaging_key: Account<'info, MessagingKey>,
+    #[account(seeds = [b"messaging_key", address.to_le_bytes().as_ref()], bump = recipient_key.bump)]
+    pub recipient_key: Account<'info, MessagingKey>,
+    // the block account of the recipient on this user must not exist
+    #[account(address = Block::pubkey(address, user_profile.uid), constraint = block.data_is_empty() @ SplingError::UserBlocked)]
+    /// CHECK: only checked for existence
+    pub block: UncheckedAccount<'info>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<Conversation>(), seeds = [b"conversation", user_profile.uid.min(address).to_le_bytes().as_ref(), user_profile.uid.max(address).to_le_bytes().as_ref()], bump)]
+    pub conversation: Account<'info, Conversation>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenConversation<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        address: u32,
+        bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_MESSAGE, None)?;
+
+        let Self {
+            user_profile,
+            conversation,
+            ..
+        } = self;
+
+        require!(address != user_profile.uid, SplingError::InvalidUser);
+
+        let clock: Clock = Clock::get().unwrap();
+        conversation.ts = clock.unix_timestamp;
+
+        // lowest user id first, in line with the seeds
+        conversation.users = [user_profile.uid.min(address), user_profile.uid.max(address)];
+        conversation.messages = 0;
+
+        // Conversation is a PDA, so here we store the bump
+        conversation.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RemoveBookmark<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // the post may be deleted already, so it's only used for the seeds
+    /// CHECK: bookmarked post account, not read
+    pub post: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"bookmark", user_profile.uid.to_le_bytes().as_ref(), post.key().as_ref()], bump = bookmark.bump, close = user)]
+    pub bookmark: Account<'info, Bookmark>,
+}
+
+impl<'info> RemoveBookmark<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        // closing the bookmark account is all there is to it
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RemoveFromCollection<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // decrease the collection account size by one public key, the rent goes back to the user
+    #[account(mut, seeds = [b"collection", user_profile.uid.to_le_bytes().as_ref(), name.as_bytes()], bump = collection.bump, realloc = Collection::space(&name, collection.posts.len().saturating_sub(1)), realloc::payer = user, realloc::zero = false)]
+    pub collection: Account<'info, Collection>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RemoveFromCollection<'info> {
+    // the post is passed by key, so deleted posts can be removed as well
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        post: Pubkey,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self { collection, .. } = self;
+
+        let position = collection
+            .posts
+            .iter()
+            .position(|key| *key == post)
+            .ok_or(SplingError::NotInCollection)?;
+        collection.posts.remove(position);
+
+        let clock: Clock = Clock::get().unwrap();
+        collection.ts = clock.unix_timestamp;
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(name: String, address: u32)]
+pub struct RemoveFromList<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"list", user_profile.uid.to_le_bytes().as_ref(), name.as_bytes()], bump = list.bump)]
+    pub list: Account<'info, List>,
+    #[account(mut, seeds = [b"list_member", list.key().as_ref(), address.to_le_bytes().as_ref()], bump = list_member.bump, close = user)]
+    pub list_member: Account<'info, ListMember>,
+}
+
+impl<'info> RemoveFromList<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self { list, .. } = self;
+
+        list.members -= 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct RemoveIssuer<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"issuer", address.as_ref()], bump = issuer.bump, close = user)]
+    pub issuer: Account<'info, Issuer>,
+}
+
+impl<'info> RemoveIssuer<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        // closing the issuer account is all there is to it
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct RemoveWallet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // only the primary wallet manages the other wallets
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, has_one = user)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"wallet", address.as_ref()], bump = wallet.bump, constraint = wallet.uid == user_profile.uid @ SplingError::WalletNotFound, close = user)]
+    pub wallet: Account<'info, Wallet>,
+}
+
+impl<'info> RemoveWallet<'_> {
+    pub fn process(&mut self, address: Pubkey) -> Result<()> {
+        let Self { user_profile, .. } = self;
+
+        // the primary wallet is not in the list, rotate first to remove it
+        let slot = user_profile
+            .wallets
+            .iter()
+            .position(|w| *w == address && address != Pubkey::default())
+            .ok_or(SplingError::WalletNotFound)?;
+        user_profile.wallets[slot] = Pubkey::default();
+        Ok(())
+    }
+}
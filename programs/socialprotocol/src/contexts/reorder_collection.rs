@@ -0,0 +1,40 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct ReorderCollection<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"collection", user_profile.uid.to_le_bytes().as_ref(), name.as_bytes()], bump = collection.bump)]
+    pub collection: Account<'info, Collection>,
+}
+
+impl<'info> ReorderCollection<'info> {
+    // move the post at position from to position to, the posts in between shift by one
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        from: u16,
+        to: u16,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self { collection, .. } = self;
+
+        let (from, to) = (from as usize, to as usize);
+        require!(
+            from < collection.posts.len() && to < collection.posts.len(),
+            SplingError::InvalidPosition
+        );
+
+        let post = collection.posts.remove(from);
+        collection.posts.insert(to, post);
+
+        let clock: Clock = Clock::get().unwrap();
+        collection.ts = clock.unix_timestamp;
+        Ok(())
+    }
+}
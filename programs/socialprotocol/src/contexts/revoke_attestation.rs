@@ -0,0 +1,38 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(uid: u32, kind: u8)]
+pub struct RevokeAttestation<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    // the issuer of the attestation or the admin can revoke it
+    #[account(mut, seeds = [b"attestation", uid.to_le_bytes().as_ref(), kind.to_le_bytes().as_ref()], bump = attestation.bump, constraint = attestation.issuer == user.key() || config.admin == user.key() @ SplingError::Unauthorized)]
+    pub attestation: Account<'info, Attestation>,
+}
+
+impl<'info> RevokeAttestation<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        let Self {
+            user,
+            config,
+            attestation,
+        } = self;
+
+        // the account is kept, so the revocation stays on record
+        attestation.revoked = true;
+        if config.admin == user.key() {
+            attestation.admin_revoked = true;
+        }
+
+        let clock: Clock = Clock::get().unwrap();
+        emit!(AttestationRevoked {
+            uid: attestation.uid,
+            kind: attestation.kind,
+            issuer: attestation.issuer,
+            revoker: user.key(),
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(session: Pubkey)]
+pub struct RevokeSessionKey<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // only a wallet of the profile can revoke session keys, also before they expire
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"session_key", user_profile.uid.to_le_bytes().as_ref(), session.as_ref()], bump = session_key.bump, close = user)]
+    pub session_key: Account<'info, SessionKey>,
+}
+
+impl<'info> RevokeSessionKey<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        // closing the session key account is all there is to it
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RotatePrimaryWallet<'info> {
+    pub user: Signer<'info>,
+    // only the primary wallet hands over, a secondary wallet taking over would skip the
+    // delay of a guardian recovery
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, has_one = user)]
+    pub user_profile: Account<'info, UserProfile>,
+}
+
+impl<'info> RotatePrimaryWallet<'_> {
+    // the primary wallet hands over to another wallet of the profile
+    pub fn process(&mut self, address: Pubkey) -> Result<()> {
+        let Self { user_profile, .. } = self;
+
+        // the previous primary wallet takes the place of the new one
+        let slot = user_profile
+            .wallets
+            .iter()
+            .position(|w| *w == address && address != Pubkey::default())
+            .ok_or(SplingError::WalletNotFound)?;
+        let previous = user_profile.user;
+        user_profile.wallets[slot] = previous;
+        user_profile.user = address;
+
+        let clock: Clock = Clock::get().unwrap();
+        emit!(PrimaryWalletRotated {
+            uid: user_profile.uid,
+            previous,
+            primary: address,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
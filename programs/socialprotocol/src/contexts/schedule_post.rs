@@ -0,0 +1,210 @@
+use crate::*;
+use anchor_lang::solana_program::instruction::Instruction;
+
+use clockwork_sdk::{
+    state::{Thread, Trigger},
+    ThreadProgram,
+};
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(group_id: u32, shdw: Pubkey)]
+pub struct SchedulePost<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut, seeds = [b"spling"], bump = spling.bump)]
+    pub spling: Account<'info, Spling>,
+    // retrieve user id and check if signer is the owner of this user id
+    // count the post towards the badges of the user
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    // scheduled posts count towards the post rate limit
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<RateLimit>(), seeds = [b"rate_limit", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub rate_limit: Box<Account<'info, RateLimit>>,
+    // create new post account, use shdw argument as seed
+    #[account(init, payer = user, space = 8 + mem::size_of::<Post>(), seeds = [b"post".as_ref(), shdw.as_ref()], bump)]
+    pub post: Account<'info, Post>,
+    // tags of the post count towards the trending leaderboard
+    #[account(mut, seeds = [b"trending"], bump = trending.bump)]
+    pub trending: Box<Account<'info, Trending>>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<Likes>(), seeds = [b"likes".as_ref(), post.key().as_ref()], bump)]
+    pub likes: Account<'info, Likes>,
+    #[account(mut, address = Thread::pubkey(post.key(), "publish_thread".to_string()))]
+    pub publish_thread: SystemAccount<'info>,
+    #[account(address = ThreadProgram::id())]
+    pub thread_program: Program<'info, ThreadProgram>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SchedulePost<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        group_id: u32,
+        shdw: Pubkey,
+        tag_names: Vec<String>,
+        mentions: Vec<u32>,
+        publish_at: i64,
+        post_bump: u8,
+        likes_bump: u8,
+        rate_limit_bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_POST, None)?;
+
+        let Self {
+            spling,
+            config,
+            rate_limit,
+            user_profile,
+            user,
+            post,
+            likes,
+            trending,
+            publish_thread,
+            thread_program,
+            system_program,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+
+        require!(
+            publish_at >= clock.unix_timestamp + MIN_SCHEDULE_DELAY,
+            SplingError::ScheduleTooSoon
+        );
+        require!(
+            publish_at <= clock.unix_timestamp + MAX_SCHEDULE_DELAY,
+            SplingError::ScheduleTooLate
+        );
+
+        // stakers pass their stake position in the remaining accounts for the perks
+        let staked = StakePosition::staked(remaining_accounts, user_profile.uid)?;
+
+        // enforce the post rate limit of this user
+        rate_limit.uid = user_profile.uid;
+        rate_limit.bump = rate_limit_bump;
+        rate_limit.posts.record(
+            &config.limit_for(
+                &config.post_limit,
+                user_profile,
+                staked,
+                clock.unix_timestamp,
+            ),
+            clock.unix_timestamp,
+        )?;
+
+        // timestamp (ts) is the scheduled time, until the post is published
+        post.ts = publish_at;
+
+        // store the id of the user
+        post.uid = user_profile.uid;
+        user_profile.posts += 1;
+        user_profile.live_posts += 1;
+
+        // the first post earns a badge, its account is passed in the remaining accounts
+        if user_profile.posts == 1 {
+            Badge::award(
+                remaining_accounts,
+                user_profile.uid,
+                BADGE_FIRST_POST,
+                &user.to_account_info(),
+                &system_program.to_account_info(),
+            )?;
+        }
+
+        // store the group in which this post will be posted
+        post.gid = group_id;
+
+        // the post id (pid) is assigned when the post is published
+
+        // tag accounts and their post index accounts are passed in the remaining accounts
+        let post_key = post.key();
+        post.add_tags(
+            post_key,
+            &tag_names,
+            config.max_tags(staked),
+            remaining_accounts,
+            &user.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
+
+        post.add_mentions(&mentions, spling.users)?;
+
+        // start out with 0 likes
+        likes.counter = 0;
+
+        post.st = POST_STATUS_SCHEDULED;
+
+        // Likes is a PDA, so here we store the bump
+        likes.bump = likes_bump;
+
+        // the user pays the rent of the post and likes, so it's refunded on close
+        post.payer = *user.key;
+
+        // Post is a PDA, so here we store the bump
+        post.bump = post_bump;
+
+        // publish post at the scheduled time
+        let mut publish_post_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(shdw, false),
+                AccountMeta::new(spling.key(), false),
+                AccountMeta::new(post.key(), false),
+                AccountMeta::new(trending.key(), false),
+                AccountMeta::new(user.key(), false),
+                AccountMeta::new(publish_thread.key(), true),
+                AccountMeta::new_readonly(thread_program.key(), false),
+                AccountMeta::new_readonly(system_program.key(), false),
+            ],
+            data: clockwork_sdk::utils::anchor_sighash("publish_post").into(),
+        };
+
+        // tags of the post count towards the trending leaderboard once it is published
+        for id in post.tags.iter().filter(|id| **id != 0) {
+            let tag = Tag::find(remaining_accounts, *id)?;
+            publish_post_ix
+                .accounts
+                .push(AccountMeta::new(tag.key(), false));
+        }
+
+        // inboxes of mentioned users are notified when the post is published
+        for uid in post
+            .mentions
+            .iter()
+            .filter(|uid| **uid != 0 && **uid != post.uid)
+        {
+            let inbox = Inbox::find(remaining_accounts, *uid)?;
+            publish_post_ix
+                .accounts
+                .push(AccountMeta::new(inbox.key(), false));
+        }
+
+        // clockwork
+        clockwork_sdk::cpi::thread_create(
+            CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadCreate {
+                    authority: post.to_account_info(),
+                    payer: user.to_account_info(),
+                    system_program: system_program.to_account_info(),
+                    thread: publish_thread.to_account_info(),
+                },
+                &[&[b"post", shdw.as_ref(), &[post_bump]]],
+            ),
+            "publish_thread".to_string(),
+            publish_post_ix.into(),
+            Trigger::Cron {
+                schedule: schedule_at(publish_at),
+                skippable: false,
+            },
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::*;
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(address: u32)]
+pub struct SendMessage<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // the recipient may have blocked this user after the conversation was opened
+    #[account(address = Block::pubkey(address, user_profile.uid), constraint = block.data_is_empty() @ SplingError::UserBlocked)]
+    /// CHECK: only checked for existence
+    pub block: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"conversation", user_profile.uid.min(address).to_le_bytes().as_ref(), user_profile.uid.max(address).to_le_bytes().as_ref()], bump = conversation.bump)]
+    pub conversation: Account<'info, Conversation>,
+    // message n of a conversation is seeded by the conversation and its message count
+    #[account(init, payer = user, space = 8 + mem::size_of::<Message>(), seeds = [b"message", conversation.key().as_ref(), conversation.messages.to_le_bytes().as_ref()], bump)]
+    pub message: Account<'info, Message>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SendMessage<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        address: u32,
+        shdw: Pubkey,
+        nonce: [u8; 24],
+        bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_MESSAGE, None)?;
+
+        let Self {
+            user_profile,
+            conversation,
+            message,
+            ..
+        } = self;
+
+        require!(address != user_profile.uid, SplingError::InvalidUser);
+
+        let clock: Clock = Clock::get().unwrap();
+        message.ts = clock.unix_timestamp;
+
+        message.conversation = conversation.key();
+        message.index = conversation.messages;
+        message.uid = user_profile.uid;
+        message.shdw = shdw;
+        message.nonce = nonce;
+
+        // Message is a PDA, so here we store the bump
+        message.bump = bump;
+
+        // increment the message count, to reflect the newly sent message
+        conversation.messages += 1;
+        conversation.ts = message.ts;
+
+        emit!(MessageSent {
+            conversation: conversation.key(),
+            message: message.key(),
+            index: message.index,
+            uid: message.uid,
+            to: address,
+            ts: message.ts,
+        });
+
+        // the inbox of the recipient is passed in the remaining accounts
+        Inbox::notify(
+            remaining_accounts,
+            address,
+            Notification {
+                kind: NOTIFICATION_MESSAGE,
+                uid: user_profile.uid,
+                key: message.key(),
+                ts: message.ts,
+            },
+        )
+    }
+}
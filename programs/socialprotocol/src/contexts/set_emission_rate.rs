@@ -0,0 +1,26 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetEmissionRate<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut, seeds = [b"reward_pool"], bump = reward_pool.bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+}
+
+impl<'info> SetEmissionRate<'_> {
+    // the rate applies to the current epoch, as emissions are fixed when an epoch ends
+    pub fn process(&mut self, emission: u64, epoch_length: i64) -> Result<()> {
+        let Self { reward_pool, .. } = self;
+
+        require!(
+            (MIN_REWARD_EPOCH..=MAX_REWARD_EPOCH).contains(&epoch_length),
+            SplingError::InvalidEpochLength
+        );
+
+        reward_pool.emission = emission;
+        reward_pool.epoch_length = epoch_length;
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // guardians can move the profile to a new wallet, so only the primary wallet names them
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, has_one = user)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<Guardians>(), seeds = [b"guardians", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub guardians: Account<'info, Guardians>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetGuardians<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        list: Vec<Guardian>,
+        threshold: u8,
+        delay: i64,
+        bump: u8,
+    ) -> Result<()> {
+        let Self {
+            user_profile,
+            guardians,
+            ..
+        } = self;
+
+        // approvals count against the current guardians, so they can't change during a recovery
+        require!(!guardians.pending, SplingError::RecoveryPending);
+        Guardians::check(user_profile, &list, threshold, delay, remaining_accounts)?;
+
+        guardians.uid = user_profile.uid;
+        guardians.guardians = [Guardian::default(); MAX_GUARDIANS];
+        guardians.guardians[..list.len()].copy_from_slice(&list);
+        guardians.threshold = threshold;
+        guardians.delay = delay;
+
+        // Guardians is a PDA, so here we store the bump
+        guardians.bump = bump;
+
+        let clock: Clock = Clock::get().unwrap();
+        emit!(GuardiansSet {
+            uid: user_profile.uid,
+            guardians: list.len() as u8,
+            threshold,
+            delay,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
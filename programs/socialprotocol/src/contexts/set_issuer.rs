@@ -0,0 +1,35 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct SetIssuer<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // only the protocol admin manages the issuers
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Account<'info, Config>,
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<Issuer>(), seeds = [b"issuer", address.as_ref()], bump)]
+    pub issuer: Account<'info, Issuer>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetIssuer<'_> {
+    pub fn process(&mut self, address: Pubkey, kinds: u32, bump: u8) -> Result<()> {
+        let Self { issuer, .. } = self;
+
+        // bit 0 is not a kind
+        require!(kinds & 1 == 0, SplingError::InvalidAttestationKind);
+
+        // attestations of kinds that are taken away are no longer valid, see Attestation::is_valid
+        issuer.issuer = address;
+        issuer.kinds = kinds;
+
+        let clock: Clock = Clock::get().unwrap();
+        issuer.ts = clock.unix_timestamp;
+
+        // Issuer is a PDA, so here we store the bump
+        issuer.bump = bump;
+        Ok(())
+    }
+}
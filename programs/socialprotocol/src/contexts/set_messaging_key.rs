@@ -0,0 +1,34 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetMessagingKey<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    // created on first use, a later call rotates the key
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<MessagingKey>(), seeds = [b"messaging_key", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub messaging_key: Account<'info, MessagingKey>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetMessagingKey<'_> {
+    pub fn process(&mut self, key: [u8; 32], bump: u8) -> Result<()> {
+        let Self {
+            user_profile,
+            messaging_key,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        messaging_key.ts = clock.unix_timestamp;
+
+        messaging_key.uid = user_profile.uid;
+        messaging_key.key = key;
+
+        // MessagingKey is a PDA, so here we store the bump
+        messaging_key.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetProtected<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    // created on first use
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<ProfileSettings>(), seeds = [b"profile_settings", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub profile_settings: Account<'info, ProfileSettings>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetProtected<'_> {
+    pub fn process(&mut self, protected: bool, bump: u8) -> Result<()> {
+        let Self {
+            user_profile,
+            profile_settings,
+            ..
+        } = self;
+
+        profile_settings.uid = user_profile.uid;
+
+        // existing approved followers stay approved when protection is turned off and on again
+        profile_settings.protected = protected;
+
+        // ProfileSettings is a PDA, so here we store the bump
+        profile_settings.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(shdw: Pubkey)]
+pub struct SetSubscribersOnly<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    // only the author decides who can like, reply to and vote on a post
+    #[account(mut, seeds = [b"post".as_ref(), shdw.as_ref()], bump = post.bump, constraint = post.uid == user_profile.uid @ SplingError::NotAuthorized)]
+    pub post: Account<'info, Post>,
+}
+
+impl<'info> SetSubscribersOnly<'_> {
+    pub fn process(&mut self, subscribers_only: bool) -> Result<()> {
+        let Self { post, .. } = self;
+
+        // the content itself lives in shadow storage, so this gates interactions only
+        post.subscribers_only = subscribers_only;
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetSubscriptionTier<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    // one tier per creator, created the first time it is set
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<SubscriptionTier>(), seeds = [b"subscription_tier", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub subscription_tier: Box<Account<'info, SubscriptionTier>>,
+    // payments go to a token account of the creator, subscribers pay in its mint
+    #[account(constraint = user_profile.is_wallet(&token_account.owner) @ SplingError::NotAuthorized)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetSubscriptionTier<'_> {
+    pub fn process(&mut self, price: u64, period: i64, bump: u8) -> Result<()> {
+        let Self {
+            user_profile,
+            subscription_tier,
+            token_account,
+            ..
+        } = self;
+
+        require!(price > 0, SplingError::InvalidSubscriptionPrice);
+        require!(
+            (SUBSCRIPTION_DAY..=MAX_SUBSCRIPTION_PERIOD).contains(&period)
+                && period % SUBSCRIPTION_DAY == 0,
+            SplingError::InvalidSubscriptionPeriod
+        );
+
+        // existing subscribers keep the terms they subscribed with
+        subscription_tier.uid = user_profile.uid;
+        subscription_tier.mint = token_account.mint;
+        subscription_tier.token_account = token_account.key();
+        subscription_tier.price = price;
+        subscription_tier.period = period;
+
+        // SubscriptionTier is a PDA, so here we store the bump
+        subscription_tier.bump = bump;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetupConfig<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut, seeds = [b"spling"], bump = spling.bump)]
+    pub spling: Account<'info, Spling>,
+    // the signer that sets up the config becomes the protocol admin
+    #[account(init, payer = user, space = 8 + mem::size_of::<Config>(), seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+    // only the upgrade authority of the program can set up the config
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Socialprotocol>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(user.key()) @ SplingError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetupConfig<'_> {
+    pub fn process(
+        &mut self,
+        post_limit: ActionLimit,
+        reply_limit: ActionLimit,
+        like_limit: ActionLimit,
+        boost: u16,
+        bump: u8,
+    ) -> Result<()> {
+        let Self { config, user, .. } = self;
+
+        config.admin = *user.key;
+
+        config.post_limit = post_limit;
+        config.reply_limit = reply_limit;
+        config.like_limit = like_limit;
+        config.boost = boost;
+
+        // Config is a PDA, so here we store the bump
+        config.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetupReferralVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Account<'info, Config>,
+    // the vault is its own authority, so only the program pays out of it
+    #[account(init, payer = user, seeds = [b"referral_vault"], bump, token::mint = mint, token::authority = referral_vault)]
+    pub referral_vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> SetupReferralVault<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        // creating the vault is all there is to it
+        Ok(())
+    }
+}
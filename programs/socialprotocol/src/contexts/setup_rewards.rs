@@ -0,0 +1,45 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetupRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // only the protocol admin sets up the reward pool
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Box<Account<'info, Config>>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<RewardPool>(), seeds = [b"reward_pool"], bump)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    // the vault is its own authority, so only the program pays out of it
+    #[account(init, payer = user, seeds = [b"reward_vault"], bump, token::mint = mint, token::authority = reward_vault)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> SetupRewards<'_> {
+    pub fn process(&mut self, emission: u64, epoch_length: i64, bump: u8) -> Result<()> {
+        let Self {
+            reward_pool, mint, ..
+        } = self;
+
+        require!(
+            (MIN_REWARD_EPOCH..=MAX_REWARD_EPOCH).contains(&epoch_length),
+            SplingError::InvalidEpochLength
+        );
+
+        let clock: Clock = Clock::get().unwrap();
+
+        reward_pool.mint = mint.key();
+        reward_pool.emission = emission;
+        reward_pool.epoch_length = epoch_length;
+        reward_pool.epoch = 0;
+        reward_pool.epoch_start = clock.unix_timestamp;
+
+        // RewardPool is a PDA, so here we store the bump
+        reward_pool.bump = bump;
+        Ok(())
+    }
+}
@@ -12,7 +12,7 @@ pub struct SetupSpling<'info> {
 
 impl<'info> SetupSpling<'_> {
     pub fn process(&mut self, bump: u8) -> Result<()> {
-        let Self { spling, user, .. } = self;
+        let Self { spling, .. } = self;
 
         // start with 0 - when a new user signs up/group is created, increments with 1
         spling.users = 0;
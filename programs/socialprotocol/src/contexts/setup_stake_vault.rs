@@ -0,0 +1,24 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetupStakeVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Box<Account<'info, Config>>,
+    // the vault is its own authority, so only the program pays out of it
+    #[account(init, payer = user, seeds = [b"stake_vault"], bump, token::mint = mint, token::authority = stake_vault)]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> SetupStakeVault<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        // creating the vault is all there is to it
+        Ok(())
+    }
+}
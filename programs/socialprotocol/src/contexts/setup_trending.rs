@@ -0,0 +1,23 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetupTrending<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut, seeds = [b"spling"], bump = spling.bump)]
+    pub spling: Account<'info, Spling>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<Trending>() + TRENDING_TAGS * mem::size_of::<TrendingTag>(), seeds = [b"trending"], bump)]
+    pub trending: Account<'info, Trending>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetupTrending<'_> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let Self { trending, .. } = self;
+
+        // Trending is a PDA, so here we store the bump
+        trending.bump = bump;
+        Ok(())
+    }
+}
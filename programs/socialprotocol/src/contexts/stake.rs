@@ -0,0 +1,63 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    // one position per user, created on the first stake
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<StakePosition>(), seeds = [b"stake_position", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+    #[account(mut, seeds = [b"stake_vault"], bump)]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub sender_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Stake<'_> {
+    pub fn process(&mut self, amount: u64, bump: u8) -> Result<()> {
+        let Self {
+            user,
+            user_profile,
+            stake_position,
+            stake_vault,
+            sender_token_account,
+            token_program,
+            ..
+        } = self;
+
+        require!(amount > 0, SplingError::InvalidStakeAmount);
+
+        // transfer Spling tokens
+        let cpi_context = CpiContext::new(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: sender_token_account.to_account_info(),
+                to: stake_vault.to_account_info(),
+                authority: user.to_account_info(),
+            },
+        );
+        token::transfer(cpi_context, amount)?;
+
+        stake_position.uid = user_profile.uid;
+        stake_position.mint = stake_vault.mint;
+        stake_position.amount += amount;
+
+        // StakePosition is a PDA, so here we store the bump
+        stake_position.bump = bump;
+
+        let clock: Clock = Clock::get().unwrap();
+        emit!(StakeChanged {
+            uid: user_profile.uid,
+            amount: stake_position.amount,
+            unstaking: stake_position.unstaking,
+            unlock_at: stake_position.unlock_at,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use crate::*;
+use anchor_lang::solana_program::instruction::Instruction;
+
+use clockwork_sdk::{
+    state::{Thread, Trigger},
+    ThreadProgram,
+};
+
+#[derive(Accounts)]
+// use function arguments for pda account creation
+#[instruction(group_id: u32, shdw: Pubkey)]
+pub struct SubmitPoll<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // retrieve user id and check if signer is the owner of this user id
+    #[account(mut, seeds = [b"spling"], bump = spling.bump)]
+    pub spling: Account<'info, Spling>,
+    // count the post towards the badges of the user
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    // polls count towards the post rate limit
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<RateLimit>(), seeds = [b"rate_limit", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub rate_limit: Box<Account<'info, RateLimit>>,
+    // create new post account, use shdw argument as seed
+    #[account(init, payer = user, space = 8 + mem::size_of::<Post>(), seeds = [b"post".as_ref(), shdw.as_ref()], bump)]
+    pub post: Account<'info, Post>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<Likes>(), seeds = [b"likes".as_ref(), post.key().as_ref()], bump)]
+    pub likes: Account<'info, Likes>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<Poll>(), seeds = [b"poll".as_ref(), post.key().as_ref()], bump)]
+    pub poll: Box<Account<'info, Poll>>,
+    // votes weigh by the stake of this mint, when the poll is token weighted
+    #[account()]
+    pub mint: Account<'info, Mint>,
+    // thread accounts are only used when the poll has a close timestamp
+    #[account(mut, address = Thread::pubkey(post.key(), "poll_thread".to_string()))]
+    /// CHECK: thread account, only created when the poll closes
+    pub poll_thread: UncheckedAccount<'info>,
+    #[account(address = ThreadProgram::id())]
+    /// CHECK: thread program, only invoked when the poll closes
+    pub thread_program: UncheckedAccount<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitPoll<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        group_id: u32,
+        shdw: Pubkey,
+        options: u8,
+        closes_at: Option<i64>,
+        token_weighted: bool,
+        post_bump: u8,
+        likes_bump: u8,
+        poll_bump: u8,
+        rate_limit_bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_POST, None)?;
+
+        let Self {
+            spling,
+            config,
+            rate_limit,
+            user_profile,
+            user,
+            post,
+            likes,
+            poll,
+            mint,
+            poll_thread,
+            thread_program,
+            system_program,
+            ..
+        } = self;
+
+        require!(
+            options >= MIN_POLL_OPTIONS && options as usize <= MAX_POLL_OPTIONS,
+            SplingError::InvalidPollOptions
+        );
+
+        // load the clock to create a creation date timestamp (ts)
+        let clock: Clock = Clock::get().unwrap();
+        post.ts = clock.unix_timestamp;
+
+        // stakers pass their stake position in the remaining accounts for the perks
+        let staked = StakePosition::staked(remaining_accounts, user_profile.uid)?;
+
+        // enforce the post rate limit of this user
+        rate_limit.uid = user_profile.uid;
+        rate_limit.bump = rate_limit_bump;
+        rate_limit.posts.record(
+            &config.limit_for(
+                &config.post_limit,
+                user_profile,
+                staked,
+                clock.unix_timestamp,
+            ),
+            clock.unix_timestamp,
+        )?;
+
+        // store the id of the user
+        post.uid = user_profile.uid;
+        user_profile.posts += 1;
+        user_profile.live_posts += 1;
+
+        // the first post earns a badge, its account is passed in the remaining accounts
+        if user_profile.posts == 1 {
+            Badge::award(
+                remaining_accounts,
+                user_profile.uid,
+                BADGE_FIRST_POST,
+                &user.to_account_info(),
+                &system_program.to_account_info(),
+            )?;
+        }
+
+        // store the group in which this poll is posted
+        post.gid = group_id;
+
+        post.pid = &spling.posts + 1;
+
+        // increment post spling with 1, to reflect the newly created post
+        spling.posts += 1;
+
+        // start out with 0 likes
+        likes.counter = 0;
+
+        post.st = POST_STATUS_ACTIVE;
+
+        // Likes is a PDA, so here we store the bump
+        likes.bump = likes_bump;
+
+        // the user pays the rent of the post, likes and poll, so it's refunded on close
+        post.payer = *user.key;
+        poll.payer = *user.key;
+
+        // Post is a PDA, so here we store the bump
+        post.bump = post_bump;
+
+        poll.post = post.key();
+        poll.options = options;
+        poll.voters = 0;
+        poll.closed = false;
+        if token_weighted {
+            // the stake of voters is locked until the poll closes
+            require!(closes_at.is_some(), SplingError::WeightedPollNeverCloses);
+            poll.mint = mint.key();
+        }
+
+        // Poll is a PDA, so here we store the bump
+        poll.bump = poll_bump;
+
+        emit!(PostPublished {
+            post: post.key(),
+            pid: post.pid,
+            uid: post.uid,
+            gid: post.gid,
+            ts: post.ts,
+        });
+
+        // without a close timestamp the poll stays open
+        if let Some(closes_at) = closes_at {
+            require!(
+                closes_at >= clock.unix_timestamp + MIN_POLL_DURATION
+                    && closes_at <= clock.unix_timestamp + MAX_POLL_DURATION,
+                SplingError::InvalidPollClose
+            );
+
+            poll.closes_at = closes_at;
+
+            // close poll at the close timestamp
+            let close_poll_ix = Instruction {
+                program_id: crate::ID,
+                accounts: vec![
+                    AccountMeta::new_readonly(shdw, false),
+                    AccountMeta::new_readonly(post.key(), false),
+                    AccountMeta::new(poll.key(), false),
+                    AccountMeta::new(user.key(), false),
+                    AccountMeta::new(poll_thread.key(), true),
+                    AccountMeta::new_readonly(thread_program.key(), false),
+                    AccountMeta::new_readonly(system_program.key(), false),
+                ],
+                data: clockwork_sdk::utils::anchor_sighash("close_poll").into(),
+            };
+
+            // clockwork
+            clockwork_sdk::cpi::thread_create(
+                CpiContext::new_with_signer(
+                    thread_program.to_account_info(),
+                    clockwork_sdk::cpi::ThreadCreate {
+                        authority: post.to_account_info(),
+                        payer: user.to_account_info(),
+                        system_program: system_program.to_account_info(),
+                        thread: poll_thread.to_account_info(),
+                    },
+                    &[&[b"post", shdw.as_ref(), &[post_bump]]],
+                ),
+                "poll_thread".to_string(),
+                close_poll_ix.into(),
+                Trigger::Cron {
+                    schedule: schedule_at(closes_at),
+                    skippable: false,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+}
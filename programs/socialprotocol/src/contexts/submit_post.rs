@@ -15,44 +15,63 @@ pub struct SubmitPost<'info> {
     // retrieve user id and check if signer is the owner of this user id
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
-    #[account(seeds = [b"user_profile", user.key().as_ref()], bump = user_profile.bump, has_one = user)]
+    // count the post towards the badges of the user
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
     pub user_profile: Account<'info, UserProfile>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    // per user rate state, created on the first rate limited action
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<RateLimit>(), seeds = [b"rate_limit", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub rate_limit: Box<Account<'info, RateLimit>>,
     // create new post account, use shdw argument as seed
     #[account(init, payer = user, space = 8 + mem::size_of::<Post>(), seeds = [b"post".as_ref(), shdw.as_ref()], bump)]
     pub post: Account<'info, Post>,
-    #[account(mut, seeds = [b"tags".as_ref()], bump = tags.bump)]
-    pub tags: Account<'info, Tags>,
+    // tags of the post count towards the trending leaderboard
+    #[account(mut, seeds = [b"trending"], bump = trending.bump)]
+    pub trending: Box<Account<'info, Trending>>,
     #[account(init, payer = user, space = 8 + mem::size_of::<Likes>(), seeds = [b"likes".as_ref(), post.key().as_ref()], bump)]
     pub likes: Account<'info, Likes>,
     #[account(mut)]
     pub b: Account<'info, B>,
-    #[account(mut,address = Thread::pubkey(post.key(),"post_thread".to_string()))]
-    pub post_thread: SystemAccount<'info>,
+    // thread accounts are only used when the post expires, so the program
+    // also works on clusters without the thread program
+    #[account(mut, address = Thread::pubkey(post.key(), "post_thread".to_string()))]
+    /// CHECK: thread account, only created when an expiry is requested
+    pub post_thread: UncheckedAccount<'info>,
     #[account(address = ThreadProgram::id())]
-    pub thread_program: Program<'info, ThreadProgram>,
+    /// CHECK: thread program, only invoked when an expiry is requested
+    pub thread_program: UncheckedAccount<'info>,
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> SubmitPost<'_> {
+impl<'info> SubmitPost<'info> {
     pub fn process(
         &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
         group_id: u32,
         shdw: Pubkey,
-        tag_name: String,
+        tag_names: Vec<String>,
+        mentions: Vec<u32>,
         amount: Option<u64>,
         post_bump: u8,
         likes_bump: u8,
-        schedule: String,
+        rate_limit_bump: u8,
+        expiry: Option<i64>,
     ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_POST, amount)?;
+
         let Self {
             spling,
+            config,
+            rate_limit,
             user_profile,
             user,
-            b,
             post,
             likes,
-            tags,
+            trending,
             post_thread,
             thread_program,
             system_program,
@@ -63,9 +82,38 @@ impl<'info> SubmitPost<'_> {
         let clock: Clock = Clock::get().unwrap();
         post.ts = clock.unix_timestamp;
 
+        // stakers pass their stake position in the remaining accounts for the perks
+        let staked = StakePosition::staked(remaining_accounts, user_profile.uid)?;
+
+        // enforce the post rate limit of this user
+        rate_limit.uid = user_profile.uid;
+        rate_limit.bump = rate_limit_bump;
+        rate_limit.posts.record(
+            &config.limit_for(
+                &config.post_limit,
+                user_profile,
+                staked,
+                clock.unix_timestamp,
+            ),
+            clock.unix_timestamp,
+        )?;
+
         // store the id of the user
         let uid: u32 = user_profile.uid;
         post.uid = uid;
+        user_profile.posts += 1;
+        user_profile.live_posts += 1;
+
+        // the first post earns a badge, its account is passed in the remaining accounts
+        if user_profile.posts == 1 {
+            Badge::award(
+                remaining_accounts,
+                user_profile.uid,
+                BADGE_FIRST_POST,
+                &user.to_account_info(),
+                &system_program.to_account_info(),
+            )?;
+        }
 
         // store the group in which this post is posted
         post.gid = group_id;
@@ -75,70 +123,95 @@ impl<'info> SubmitPost<'_> {
         // increment post spling with 1, to reflect the newly created post
         spling.posts += 1;
 
-        if tag_name.is_empty() {
-        } else {
-            let tag_name_norm = &tag_name.to_lowercase();
-
-            // check if tag already exists
-            if tags.taglist.contains(&tag_name_norm) {
-                let index = tags
-                    .taglist
-                    .iter()
-                    .position(|r| r == tag_name_norm)
-                    .unwrap();
-                post.tid = index as u16;
-            } else {
-                tags.taglist.push(String::from(tag_name_norm));
-                post.tid = &spling.tags + 1;
-                spling.tags = &spling.tags + 1;
-            }
-        }
+        // tag accounts and their post index accounts are passed in the remaining accounts
+        let post_key = post.key();
+        post.add_tags(
+            post_key,
+            &tag_names,
+            config.max_tags(staked),
+            remaining_accounts,
+            &user.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
+        post.credit_tags(trending, remaining_accounts, clock.unix_timestamp)?;
+
+        post.add_mentions(&mentions, spling.users)?;
 
         // start out with 0 likes
         likes.counter = 0;
 
         // status (st) is standard 1, can have future utility for moderation purposes
-        post.st = 1;
+        post.st = POST_STATUS_ACTIVE;
 
         // Likes is a PDA, so here we store the bump
         likes.bump = likes_bump;
 
+        // the user pays the rent of the post and likes, so it's refunded on close
+        post.payer = *user.key;
+
         // Post is a PDA, so here we store the bump
         post.bump = post_bump;
 
-        // close post after some time
-        let clockwork_delete_post_ix = Instruction {
-            program_id: crate::ID,
-            accounts: vec![
-                AccountMeta::new_readonly(shdw, false),
-                AccountMeta::new(spling.key(), false),
-                AccountMeta::new(post.key(), false),
-                AccountMeta::new(post_thread.key(), true),
-                AccountMeta::new_readonly(thread_program.key(), false),
-                AccountMeta::new_readonly(system_program.key(), false),
-            ],
-            data: clockwork_sdk::utils::anchor_sighash("clockwork_delete_post").into(),
-        };
-
-        // clockwork
-        clockwork_sdk::cpi::thread_create(
-            CpiContext::new_with_signer(
-                thread_program.to_account_info(),
-                clockwork_sdk::cpi::ThreadCreate {
-                    authority: post.to_account_info(),
-                    payer: user.to_account_info(),
-                    system_program: system_program.to_account_info(),
-                    thread: post_thread.to_account_info(),
+        emit!(PostPublished {
+            post: post.key(),
+            pid: post.pid,
+            uid: post.uid,
+            gid: post.gid,
+            ts: post.ts,
+        });
+        post.emit_mentions(post.key(), remaining_accounts)?;
+
+        // without an expiry the post stays until it is deleted by the user
+        if let Some(expiry) = expiry {
+            require!(
+                expiry >= clock.unix_timestamp + MIN_POST_LIFETIME,
+                SplingError::PostExpiryTooSoon
+            );
+            require!(
+                expiry <= clock.unix_timestamp + MAX_POST_LIFETIME,
+                SplingError::PostExpiryTooLate
+            );
+
+            post.expiry = expiry;
+
+            // close post at the expiry timestamp
+            let clockwork_delete_post_ix = Instruction {
+                program_id: crate::ID,
+                accounts: vec![
+                    AccountMeta::new_readonly(shdw, false),
+                    AccountMeta::new(spling.key(), false),
+                    AccountMeta::new(post.key(), false),
+                    AccountMeta::new(likes.key(), false),
+                    AccountMeta::new(trending.key(), false),
+                    AccountMeta::new(user.key(), false),
+                    AccountMeta::new(user_profile.key(), false),
+                    AccountMeta::new(post_thread.key(), true),
+                    AccountMeta::new_readonly(thread_program.key(), false),
+                    AccountMeta::new_readonly(system_program.key(), false),
+                ],
+                data: clockwork_sdk::utils::anchor_sighash("clockwork_delete_post").into(),
+            };
+
+            // clockwork
+            clockwork_sdk::cpi::thread_create(
+                CpiContext::new_with_signer(
+                    thread_program.to_account_info(),
+                    clockwork_sdk::cpi::ThreadCreate {
+                        authority: post.to_account_info(),
+                        payer: user.to_account_info(),
+                        system_program: system_program.to_account_info(),
+                        thread: post_thread.to_account_info(),
+                    },
+                    &[&[b"post", shdw.as_ref(), &[post_bump]]],
+                ),
+                "post_thread".to_string(),
+                clockwork_delete_post_ix.into(),
+                Trigger::Cron {
+                    schedule: schedule_at(expiry),
+                    skippable: false,
                 },
-                &[&[b"post", &shdw.as_ref(), &[post_bump]]],
-            ),
-            "post_thread".to_string(),
-            clockwork_delete_post_ix.into(),
-            Trigger::Cron {
-                schedule,
-                skippable: false,
-            },
-        )?;
+            )?;
+        }
 
         Ok(())
     }
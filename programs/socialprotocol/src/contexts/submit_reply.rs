@@ -9,9 +9,33 @@ pub struct SubmitReply<'info> {
     // retrieve user id and check if signer is the owner of this user id
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
-    #[account(seeds = [b"user_profile", user.key().as_ref()], bump = user_profile.bump, has_one = user)]
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
     pub user_profile: Account<'info, UserProfile>,
-    // create new post account, use shdw argument as seed
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    // per user rate state, created on the first rate limited action
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<RateLimit>(), seeds = [b"rate_limit", user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub rate_limit: Box<Account<'info, RateLimit>>,
+    // the post that is replied to, so the reply can be cleaned up with it
+    #[account(constraint = post.pid == post_id, constraint = post.st == POST_STATUS_ACTIVE @ SplingError::PostNotPublished)]
+    pub post: Account<'info, Post>,
+    // settings of the author and the follow request of this user, for protected accounts
+    #[account(address = ProfileSettings::pubkey(post.uid))]
+    /// CHECK: settings of the author, may not exist
+    pub author_settings: UncheckedAccount<'info>,
+    #[account(address = FollowRequest::pubkey(post.uid, user_profile.uid))]
+    /// CHECK: follow request of this user, may not exist
+    pub follow_request: UncheckedAccount<'info>,
+    // the author earns reputation, unless the profile is deleted
+    #[account(mut, address = UserProfile::pubkey(post.uid))]
+    /// CHECK: profile of the author, may not exist
+    pub author_profile: UncheckedAccount<'info>,
+    // the author earns reward points in the current epoch, once rewards are set up
+    // the creator points of the author are passed in the remaining accounts
+    #[account(mut, address = RewardPool::pubkey())]
+    /// CHECK: reward pool, may not exist
+    pub reward_pool: UncheckedAccount<'info>,
+    // create new reply account, use shdw argument as seed
     #[account(init, payer = user, space = 8 + mem::size_of::<Reply>(), seeds = [b"reply".as_ref(), shdw.as_ref()], bump)]
     pub reply: Account<'info, Reply>,
     #[account(mut)]
@@ -30,38 +54,77 @@ pub struct SubmitReply<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> SubmitReply<'_> {
+impl<'info> SubmitReply<'info> {
     pub fn process(
         &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
         post_id: u32,
-        shdw: Pubkey,
+        _shdw: Pubkey,
         amount: Option<u64>,
         bump: u8,
+        rate_limit_bump: u8,
     ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_REPLY, amount)?;
+
         let Self {
             spling,
+            config,
+            rate_limit,
             user_profile,
             user,
             b,
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
+            post,
             reply,
+            author_settings,
+            follow_request,
+            author_profile,
+            reward_pool,
+            system_program,
             ..
         } = self;
 
+        // only approved followers can reply to posts of a protected account
+        FollowRequest::check_access(post.uid, user_profile.uid, author_settings, follow_request)?;
+
         // load the clock to create a creation date timestamp (ts)
         let clock: Clock = Clock::get().unwrap();
         reply.ts = clock.unix_timestamp;
 
+        // only subscribers can reply to posts for subscribers
+        post.check_subscriber(remaining_accounts, user_profile.uid, clock.unix_timestamp)?;
+
+        // stakers pass their stake position in the remaining accounts for the perks
+        let staked = StakePosition::staked(remaining_accounts, user_profile.uid)?;
+
+        // enforce the reply rate limit of this user
+        rate_limit.uid = user_profile.uid;
+        rate_limit.bump = rate_limit_bump;
+        rate_limit.replies.record(
+            &config.limit_for(
+                &config.reply_limit,
+                user_profile,
+                staked,
+                clock.unix_timestamp,
+            ),
+            clock.unix_timestamp,
+        )?;
+
         // store the id of the user
         let uid: u32 = user_profile.uid;
         reply.uid = uid;
 
-        // store the post id which this reply relates to
+        // store the post id and account which this reply relates to
         reply.pid = post_id;
+        reply.post = post.key();
+
+        // store the public key of the profile owner as author, the signer may be a session key
+        reply.user = user_profile.user;
 
         // status (st) is standard 1, can have future utility for moderation purposes
         reply.st = 1;
@@ -69,14 +132,54 @@ impl<'info> SubmitReply<'_> {
         // Reply is a PDA, so here we store the bump
         reply.bump = bump;
 
+        // the author of the post, whose points are taken back when the reply is deleted
+        reply.post_uid = post.uid;
+
+        // replying to your own post earns no reputation or reward points, the epoch the points
+        // are earned in is stored, as only that epoch gives them back
+        reply.epoch = NO_REWARD_EPOCH;
+        if post.uid != uid {
+            UserProfile::add_reputation(author_profile, config.reputation.reply)?;
+            reply.epoch = RewardPool::accrue_points(
+                reward_pool,
+                remaining_accounts,
+                post.uid,
+                REWARD_POINTS_REPLY,
+                &user.to_account_info(),
+                &system_program.to_account_info(),
+            )?;
+        }
+
+        // the inbox of the post author is passed in the remaining accounts
+        Inbox::notify(
+            remaining_accounts,
+            post.uid,
+            Notification {
+                kind: NOTIFICATION_REPLY,
+                uid,
+                key: reply.key(),
+                ts: reply.ts,
+            },
+        )?;
+
         match amount {
+            // users below the reputation threshold pay the rent of their reply
+            None if !config.is_subsidized(user_profile, staked, clock.unix_timestamp) => {
+                reply.payer = user.key();
+            }
             None => {
+                // rent is covered by the spling subsidy, so it goes back there on close
+                reply.payer = spling.key();
+
                 // transfer SOL tokens
                 let subsidy: u64 = 2000000;
                 **spling.to_account_info().try_borrow_mut_lamports()? -= subsidy;
                 **user.try_borrow_mut_lamports()? += subsidy;
             }
             Some(am) => {
+                // the bank pays the receiver, not the rent, so it goes back to the user
+                reply.payer = user.key();
+
                 // transfer Spling tokens
                 let cpi_context = CpiContext::new(
                     token_program.to_account_info(),
@@ -0,0 +1,231 @@
+use crate::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+
+use clockwork_sdk::{
+    state::{Thread, Trigger},
+    ThreadProgram,
+};
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"subscription_tier", subscription_tier.uid.to_le_bytes().as_ref()], bump = subscription_tier.bump)]
+    pub subscription_tier: Box<Account<'info, SubscriptionTier>>,
+    // a cancelled or lapsed subscription is renewed in the same account
+    #[account(init_if_needed, payer = user, space = 8 + mem::size_of::<Subscription>(), seeds = [b"subscription", subscription_tier.uid.to_le_bytes().as_ref(), user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub subscription: Box<Account<'info, Subscription>>,
+    // the signer approves the delegate on this account, so it has to own it
+    #[account(mut, constraint = token_account.owner == user.key() @ SplingError::NotAuthorized, constraint = token_account.mint == subscription_tier.mint)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = subscription_tier.token_account)]
+    pub recipient: Box<Account<'info, TokenAccount>>,
+    // the program charges all subscriptions through this delegate
+    #[account(seeds = [b"subscription_delegate"], bump)]
+    /// CHECK: delegate PDA, holds no data
+    pub delegate: UncheckedAccount<'info>,
+    #[account(mut, address = Thread::pubkey(subscription.key(), "subscription_thread".to_string()))]
+    /// CHECK: thread account, created here
+    pub subscription_thread: UncheckedAccount<'info>,
+    // a lapsed subscription can leave its thread behind, its balance goes back to its payer
+    #[account(mut)]
+    /// CHECK: payer recorded on the subscription, checked when the thread is left behind
+    pub payer: UncheckedAccount<'info>,
+    #[account(address = ThreadProgram::id())]
+    /// CHECK: thread program
+    pub thread_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Subscribe<'_> {
+    pub fn process(&mut self, periods: u16, fees: u64, subscription_bump: u8) -> Result<()> {
+        let Self {
+            user,
+            user_profile,
+            subscription_tier,
+            subscription,
+            token_account,
+            recipient,
+            delegate,
+            subscription_thread,
+            payer,
+            thread_program,
+            token_program,
+            system_program,
+        } = self;
+
+        require!(
+            subscription_tier.uid != user_profile.uid,
+            SplingError::CannotSubscribeToSelf
+        );
+        require!(
+            subscription.status != SUBSCRIPTION_ACTIVE,
+            SplingError::SubscriptionActive
+        );
+        require!(periods > 0, SplingError::InvalidSubscriptionPeriods);
+
+        let creator = subscription_tier.uid.to_le_bytes();
+        let subscriber = user_profile.uid.to_le_bytes();
+
+        // the thread is deleted first, so a new one can be created at its address
+        if !subscription_thread.data_is_empty() {
+            require!(
+                payer.key() == subscription.payer,
+                SplingError::InvalidThreadPayer
+            );
+            clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadDelete {
+                    authority: subscription.to_account_info(),
+                    close_to: payer.to_account_info(),
+                    thread: subscription_thread.to_account_info(),
+                },
+                &[&[
+                    b"subscription",
+                    creator.as_ref(),
+                    subscriber.as_ref(),
+                    &[subscription_bump],
+                ]],
+            ))?;
+        }
+
+        let clock: Clock = Clock::get().unwrap();
+
+        subscription.creator = subscription_tier.uid;
+        subscription.subscriber = user_profile.uid;
+        subscription.mint = subscription_tier.mint;
+        subscription.token_account = token_account.key();
+        subscription.recipient = recipient.key();
+        subscription.price = subscription_tier.price;
+        subscription.period = subscription_tier.period;
+        subscription.payer = *user.key;
+        subscription.status = SUBSCRIPTION_ACTIVE;
+
+        // Subscription is a PDA, so here we store the bump
+        subscription.bump = subscription_bump;
+
+        // the first period is paid right away, unless a cancelled subscription is still paid
+        if !subscription.is_active(clock.unix_timestamp) {
+            let cpi_context = CpiContext::new(
+                token_program.to_account_info(),
+                token::Transfer {
+                    from: token_account.to_account_info(),
+                    to: recipient.to_account_info(),
+                    authority: user.to_account_info(),
+                },
+            );
+            token::transfer(cpi_context, subscription.price)?;
+
+            subscription.paid_until = clock.unix_timestamp + subscription.period;
+
+            emit!(SubscriptionCharged {
+                creator: subscription.creator,
+                subscriber: subscription.subscriber,
+                amount: subscription.price,
+                paid_until: subscription.paid_until,
+                ts: clock.unix_timestamp,
+            });
+        }
+
+        // the delegate is shared by all subscriptions of a token account, so the
+        // approval of this subscription is added to what the others have left
+        let allowance = subscription
+            .price
+            .checked_mul(periods as u64)
+            .ok_or(SplingError::InvalidSubscriptionPeriods)?;
+        let approved = if token_account.delegate == COption::Some(delegate.key()) {
+            token_account
+                .delegated_amount
+                .saturating_sub(subscription.allowance)
+        } else {
+            0
+        };
+        token::approve(
+            CpiContext::new(
+                token_program.to_account_info(),
+                token::Approve {
+                    to: token_account.to_account_info(),
+                    delegate: delegate.to_account_info(),
+                    authority: user.to_account_info(),
+                },
+            ),
+            approved.saturating_add(allowance),
+        )?;
+        subscription.allowance = allowance;
+
+        subscription_tier.subscribers += 1;
+
+        // charge the subscription at the end of every period
+        let charge_subscription_ix = Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new(subscription.key(), false),
+                AccountMeta::new(subscription_tier.key(), false),
+                AccountMeta::new(token_account.key(), false),
+                AccountMeta::new(recipient.key(), false),
+                AccountMeta::new_readonly(delegate.key(), false),
+                AccountMeta::new(user.key(), false),
+                AccountMeta::new(subscription_thread.key(), true),
+                AccountMeta::new_readonly(thread_program.key(), false),
+                AccountMeta::new_readonly(token_program.key(), false),
+            ],
+            data: clockwork_sdk::utils::anchor_sighash("charge_subscription").into(),
+        };
+
+        // clockwork
+        clockwork_sdk::cpi::thread_create(
+            CpiContext::new_with_signer(
+                thread_program.to_account_info(),
+                clockwork_sdk::cpi::ThreadCreate {
+                    authority: subscription.to_account_info(),
+                    payer: user.to_account_info(),
+                    system_program: system_program.to_account_info(),
+                    thread: subscription_thread.to_account_info(),
+                },
+                &[&[
+                    b"subscription",
+                    creator.as_ref(),
+                    subscriber.as_ref(),
+                    &[subscription_bump],
+                ]],
+            ),
+            "subscription_thread".to_string(),
+            charge_subscription_ix.into(),
+            // periods are whole days, so a daily check lands on the end of every period
+            Trigger::Cron {
+                schedule: schedule_daily(subscription.paid_until),
+                skippable: true,
+            },
+        )?;
+
+        // the thread pays the fees of its daily runs from its balance
+        if fees > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: user.to_account_info(),
+                        to: subscription_thread.to_account_info(),
+                    },
+                ),
+                fees,
+            )?;
+        }
+
+        emit!(Subscribed {
+            creator: subscription.creator,
+            subscriber: subscription.subscriber,
+            price: subscription.price,
+            period: subscription.period,
+            paid_until: subscription.paid_until,
+            ts: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
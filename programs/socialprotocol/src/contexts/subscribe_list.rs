@@ -0,0 +1,44 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SubscribeList<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // get a list account, no way to add constraint because the owner and name are unknown
+    #[account(mut, constraint = list.visibility == VISIBILITY_PUBLIC @ SplingError::ListNotPublic)]
+    pub list: Account<'info, List>,
+    #[account(init, payer = user, space = 8 + mem::size_of::<ListSubscription>(), seeds = [b"list_subscription", list.key().as_ref(), user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub list_subscription: Account<'info, ListSubscription>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubscribeList<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>], bump: u8) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self {
+            user_profile,
+            list,
+            list_subscription,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        list_subscription.ts = clock.unix_timestamp;
+
+        list_subscription.list = list.key();
+        list_subscription.list_ts = list.ts;
+        list_subscription.uid = user_profile.uid;
+
+        // ListSubscription is a PDA, so here we store the bump
+        list_subscription.bump = bump;
+
+        list.subscribers += 1;
+        Ok(())
+    }
+}
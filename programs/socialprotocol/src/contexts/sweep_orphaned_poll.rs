@@ -0,0 +1,27 @@
+use crate::*;
+use clockwork_sdk::state::Thread;
+
+#[derive(Accounts)]
+pub struct SweepOrphanedPoll<'info> {
+    // anyone can sweep a poll, no signer needed besides the fee payer
+    #[account(mut, seeds = [b"poll".as_ref(), post.key().as_ref()], bump = poll.bump, close = payer)]
+    pub poll: Box<Account<'info, Poll>>,
+    // the post of the poll has to be closed
+    #[account(address = poll.post, constraint = post.data_is_empty() @ SplingError::PostStillExists)]
+    /// CHECK: closed post account, only checked for being empty
+    pub post: UncheckedAccount<'info>,
+    // the thread would close the poll after it is swept, delete_post or close_poll delete it first
+    #[account(address = Thread::pubkey(post.key(), "poll_thread".to_string()), constraint = poll_thread.data_is_empty() @ SplingError::ThreadStillNeeded)]
+    /// CHECK: thread account of the poll, only checked for being empty
+    pub poll_thread: UncheckedAccount<'info>,
+    // rent of the poll goes back to the account that paid for it
+    #[account(mut, address = poll.payer)]
+    /// CHECK: payer recorded on the poll
+    pub payer: UncheckedAccount<'info>,
+}
+
+impl<'info> SweepOrphanedPoll<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(shdw: Pubkey)]
+pub struct SweepOrphanedReply<'info> {
+    // anyone can sweep a reply, no signer needed besides the fee payer
+    #[account(mut, seeds = [b"reply".as_ref(), shdw.as_ref()], bump = reply.bump, close = payer)]
+    pub reply: Account<'info, Reply>,
+    // the post the reply relates to has to be closed, or replaced by another post
+    #[account(address = reply.post)]
+    /// CHECK: post account, deserialized when not empty
+    pub post: UncheckedAccount<'info>,
+    // rent of the reply goes back to the account that paid for it
+    #[account(mut, address = reply.payer)]
+    /// CHECK: payer recorded on the reply
+    pub payer: UncheckedAccount<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    // as on delete_reply, the reputation and reward points of the post author are taken back
+    #[account(mut, address = UserProfile::pubkey(reply.post_uid))]
+    /// CHECK: profile of the post author, may not exist
+    pub author_profile: UncheckedAccount<'info>,
+    // the creator points of the post author are passed in the remaining accounts
+    #[account(mut, address = RewardPool::pubkey())]
+    /// CHECK: reward pool, may not exist
+    pub reward_pool: UncheckedAccount<'info>,
+}
+
+impl<'info> SweepOrphanedReply<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let Self {
+            reply,
+            post,
+            config,
+            author_profile,
+            reward_pool,
+            ..
+        } = self;
+
+        // a post can be created again at the same address, post ids are never reused
+        if !post.data_is_empty() {
+            let current: Account<Post> = Account::try_from(post)?;
+            require!(current.pid != reply.pid, SplingError::PostStillExists);
+        }
+
+        if reply.post_uid != reply.uid {
+            UserProfile::add_reputation(author_profile, -config.reputation.reply)?;
+            RewardPool::take_back_points(
+                reward_pool,
+                remaining_accounts,
+                reply.post_uid,
+                REWARD_POINTS_REPLY,
+                reply.epoch,
+            )?;
+        }
+        Ok(())
+    }
+}
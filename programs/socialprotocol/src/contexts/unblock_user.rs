@@ -0,0 +1,19 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(address: u32)]
+pub struct UnblockUser<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"block", user_profile.uid.to_le_bytes().as_ref(), address.to_le_bytes().as_ref()], bump = block.bump, close = user)]
+    pub block: Account<'info, Block>,
+}
+
+impl<'info> UnblockUser<'_> {
+    pub fn process(&mut self) -> Result<()> {
+        // closing the block account is all there is to it
+        Ok(())
+    }
+}
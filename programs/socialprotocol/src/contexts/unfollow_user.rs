@@ -1,13 +1,22 @@
 use crate::*;
 
 #[derive(Accounts)]
+#[instruction(address: u32)]
 pub struct UnfollowUser<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(mut, seeds = [b"spling"], bump = spling.bump)]
     pub spling: Account<'info, Spling>,
-    #[account(mut, seeds = [b"user_profile", user.key().as_ref()], has_one = user, bump = user_profile.bump)]
+    #[account(mut, seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
     pub user_profile: Account<'info, UserProfile>,
+    // the follow request of a protected account is closed on unfollow
+    #[account(mut, address = FollowRequest::pubkey(address, user_profile.uid))]
+    /// CHECK: follow request, may not exist
+    pub follow_request: UncheckedAccount<'info>,
+    // the wallet that paid the rent of the follow request gets it back
+    #[account(mut)]
+    /// CHECK: checked against the follow request when it exists
+    pub payer: UncheckedAccount<'info>,
     #[account(mut)]
     pub b: Account<'info, B>,
     #[account(mut)]
@@ -24,17 +33,21 @@ pub struct UnfollowUser<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> UnfollowUser<'_> {
-    pub fn process(&mut self, address: u32, amount: Option<u64>) -> Result<()> {
+impl<'info> UnfollowUser<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>], address: u32, amount: Option<u64>) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_FOLLOW, amount)?;
+
         let Self {
-            spling,
             user_profile,
+            follow_request,
+            payer,
             user,
             b,
             receiver,
             sender_token_account,
             receiver_token_account,
-            mint,
             token_program,
             ..
         } = self;
@@ -42,6 +55,12 @@ impl<'info> UnfollowUser<'_> {
         // retain all user id's except for the user id to be deleted
         user_profile.following.retain(|x| *x != address);
 
+        if !follow_request.data_is_empty() {
+            let request: Account<FollowRequest> = Account::try_from(follow_request)?;
+            require_keys_eq!(request.user, payer.key(), SplingError::InvalidFollowRequestPayer);
+            request.close(payer.to_account_info())?;
+        }
+
         match amount {
             None => (),
             Some(am) => {
@@ -0,0 +1,44 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut, seeds = [b"stake_position", user_profile.uid.to_le_bytes().as_ref()], bump = stake_position.bump)]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+}
+
+impl<'info> Unstake<'_> {
+    // unstaked tokens stop counting for the perks right away, and can be withdrawn after the cooldown
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        let Self {
+            user_profile,
+            config,
+            stake_position,
+            ..
+        } = self;
+
+        require!(
+            amount > 0 && amount <= stake_position.amount,
+            SplingError::InvalidStakeAmount
+        );
+
+        // unstaking more restarts the cooldown for all tokens in cooldown
+        let clock: Clock = Clock::get().unwrap();
+        stake_position.amount -= amount;
+        stake_position.unstaking += amount;
+        stake_position.unlock_at = clock.unix_timestamp + config.staking.cooldown;
+
+        emit!(StakeChanged {
+            uid: user_profile.uid,
+            amount: stake_position.amount,
+            unstaking: stake_position.unstaking,
+            unlock_at: stake_position.unlock_at,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
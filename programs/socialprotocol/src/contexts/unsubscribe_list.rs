@@ -0,0 +1,39 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct UnsubscribeList<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // the list may be deleted already, so it's only read when it still exists
+    #[account(mut)]
+    /// CHECK: list account, deserialized when not empty
+    pub list: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"list_subscription", list.key().as_ref(), user_profile.uid.to_le_bytes().as_ref()], bump = list_subscription.bump, close = user)]
+    pub list_subscription: Account<'info, ListSubscription>,
+}
+
+impl<'info> UnsubscribeList<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_CURATE, None)?;
+
+        let Self {
+            list,
+            list_subscription,
+            ..
+        } = self;
+
+        // a list deleted and created again under the same name doesn't count this subscription
+        if !list.data_is_empty() {
+            let mut list: Account<List> = Account::try_from(list)?;
+            if list.ts == list_subscription.list_ts {
+                list.subscribers = list.subscribers.saturating_sub(1);
+                list.exit(&crate::ID)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    // only the protocol admin can change the config
+    #[account(mut, seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateConfig<'_> {
+    pub fn process(
+        &mut self,
+        post_limit: ActionLimit,
+        reply_limit: ActionLimit,
+        like_limit: ActionLimit,
+        boost: u16,
+    ) -> Result<()> {
+        let Self { config, .. } = self;
+
+        config.post_limit = post_limit;
+        config.reply_limit = reply_limit;
+        config.like_limit = like_limit;
+        config.boost = boost;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct UpdateReferral<'info> {
+    pub user: Signer<'info>,
+    // only the protocol admin can change the referral reward
+    #[account(mut, seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateReferral<'_> {
+    pub fn process(&mut self, referral: ReferralConfig) -> Result<()> {
+        let Self { config, .. } = self;
+
+        // rewards are paid at the current reward, also for users referred earlier
+        config.referral = referral;
+        Ok(())
+    }
+}
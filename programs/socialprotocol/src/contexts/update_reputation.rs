@@ -0,0 +1,19 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct UpdateReputation<'info> {
+    pub user: Signer<'info>,
+    // only the protocol admin can change the reputation weights
+    #[account(mut, seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateReputation<'_> {
+    pub fn process(&mut self, reputation: ReputationConfig) -> Result<()> {
+        let Self { config, .. } = self;
+
+        // new weights apply to future likes, replies and moderation, earned reputation stays
+        config.reputation = reputation;
+        Ok(())
+    }
+}
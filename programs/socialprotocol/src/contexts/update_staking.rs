@@ -0,0 +1,24 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct UpdateStaking<'info> {
+    pub user: Signer<'info>,
+    // only the protocol admin can change the staking perks
+    #[account(mut, seeds = [b"config"], bump = config.bump, constraint = config.admin == user.key() @ SplingError::Unauthorized)]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateStaking<'_> {
+    pub fn process(&mut self, staking: StakingConfig) -> Result<()> {
+        let Self { config, .. } = self;
+
+        require!(
+            (0..=MAX_STAKE_COOLDOWN).contains(&staking.cooldown),
+            SplingError::InvalidStakeCooldown
+        );
+
+        // a new cooldown applies to the next unstake, earlier ones keep their unlock time
+        config.staking = staking;
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct VotePoll<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump)]
+    pub user_profile: Account<'info, UserProfile>,
+    // get a post account, no way to add constraint because shdw (hash) is unknown
+    #[account(constraint = post.st == POST_STATUS_ACTIVE @ SplingError::PostNotPublished)]
+    pub post: Account<'info, Post>,
+    #[account(mut, seeds = [b"poll".as_ref(), post.key().as_ref()], bump = poll.bump)]
+    pub poll: Box<Account<'info, Poll>>,
+    // one vote per user, a second vote fails on the existing account
+    #[account(init, payer = user, space = 8 + mem::size_of::<PollVote>(), seeds = [b"poll_vote".as_ref(), post.key().as_ref(), user_profile.uid.to_le_bytes().as_ref()], bump)]
+    pub poll_vote: Account<'info, PollVote>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> VotePoll<'info> {
+    pub fn process(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        option: u8,
+        bump: u8,
+    ) -> Result<()> {
+        // the owner or a session key allowed to act for the profile signs
+        self.user_profile
+            .authorize(self.user.key, remaining_accounts, SESSION_VOTE, None)?;
+
+        let Self {
+            user_profile,
+            post,
+            poll,
+            poll_vote,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(poll.is_open(clock.unix_timestamp), SplingError::PollClosed);
+
+        // only subscribers can vote on polls for subscribers
+        post.check_subscriber(remaining_accounts, user_profile.uid, clock.unix_timestamp)?;
+        require!(option < poll.options, SplingError::InvalidVoteOption);
+
+        // only staked tokens weigh, a wallet balance could be moved to vote again
+        let weight: u64 = if poll.mint == Pubkey::default() {
+            1
+        } else {
+            StakePosition::vote(remaining_accounts, user_profile.uid, poll)?
+        };
+
+        poll.tallies[option as usize] = poll.tallies[option as usize].saturating_add(weight);
+        poll.voters += 1;
+
+        poll_vote.post = post.key();
+        poll_vote.uid = user_profile.uid;
+        poll_vote.option = option;
+        poll_vote.weight = weight;
+        poll_vote.ts = clock.unix_timestamp;
+
+        // PollVote is a PDA, so here we store the bump
+        poll_vote.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    pub user: Signer<'info>,
+    #[account(seeds = [b"user_profile", user_profile.uid.to_le_bytes().as_ref()], bump = user_profile.bump, constraint = user_profile.is_wallet(user.key) @ SplingError::NotAuthorized)]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(mut, seeds = [b"stake_position", user_profile.uid.to_le_bytes().as_ref()], bump = stake_position.bump)]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+    #[account(mut, seeds = [b"stake_vault"], bump)]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+    // tokens go back to a wallet of the user
+    #[account(mut, constraint = token_account.mint == stake_vault.mint, constraint = user_profile.is_wallet(&token_account.owner) @ SplingError::NotAuthorized)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawStake<'_> {
+    pub fn process(&mut self, vault_bump: u8) -> Result<()> {
+        let Self {
+            user_profile,
+            stake_position,
+            stake_vault,
+            token_account,
+            token_program,
+            ..
+        } = self;
+
+        let clock: Clock = Clock::get().unwrap();
+        require!(
+            stake_position.unstaking > 0,
+            SplingError::InvalidStakeAmount
+        );
+        require!(
+            clock.unix_timestamp >= stake_position.unlock_at,
+            SplingError::StakeCoolingDown
+        );
+        require!(
+            clock.unix_timestamp >= stake_position.voted_until,
+            SplingError::StakeLockedByVote
+        );
+
+        let seeds: &[&[u8]] = &[b"stake_vault", &[vault_bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token::Transfer {
+                    from: stake_vault.to_account_info(),
+                    to: token_account.to_account_info(),
+                    authority: stake_vault.to_account_info(),
+                },
+                &[seeds],
+            ),
+            stake_position.unstaking,
+        )?;
+
+        stake_position.unstaking = 0;
+
+        emit!(StakeChanged {
+            uid: user_profile.uid,
+            amount: stake_position.amount,
+            unstaking: 0,
+            unlock_at: stake_position.unlock_at,
+            ts: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+}
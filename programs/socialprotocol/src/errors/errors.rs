@@ -0,0 +1,185 @@
+use crate::*;
+
+#[error_code]
+pub enum SplingError {
+    #[msg("Too many actions of this type in the current window, try again later")]
+    RateLimited,
+    #[msg("Action submitted too soon after the previous one")]
+    ActionTooSoon,
+    #[msg("Signer is not the protocol admin")]
+    Unauthorized,
+    #[msg("Post expiry must be at least an hour in the future")]
+    PostExpiryTooSoon,
+    #[msg("Post expiry can be at most a year in the future")]
+    PostExpiryTooLate,
+    #[msg("Scheduled post must go live at least a minute in the future")]
+    ScheduleTooSoon,
+    #[msg("Scheduled post can go live at most a year in the future")]
+    ScheduleTooLate,
+    #[msg("Post is not published yet")]
+    PostNotPublished,
+    #[msg("Post is not scheduled")]
+    PostNotScheduled,
+    #[msg("Post is scheduled for later")]
+    PostNotDue,
+    #[msg("Post has not expired yet")]
+    PostNotExpired,
+    #[msg("Thread still has work left")]
+    ThreadStillNeeded,
+    #[msg("Post of this reply still exists")]
+    PostStillExists,
+    #[msg("Tag names are 1 to 32 characters of a-z, 0-9 and _")]
+    InvalidTagName,
+    #[msg("Tag account is missing, create the tag first")]
+    TagNotFound,
+    #[msg("Tag post index account is missing")]
+    TagIndexNotFound,
+    #[msg("Too many tags on this post")]
+    TooManyTags,
+    #[msg("Tag is used twice on this post")]
+    DuplicateTag,
+    #[msg("Too many mentions on this post")]
+    TooManyMentions,
+    #[msg("Mentioned user doesn't exist or is mentioned twice")]
+    InvalidMention,
+    #[msg("Inbox account must be writable")]
+    InboxNotWritable,
+    #[msg("Inbox account of the notified user is missing")]
+    InboxMissing,
+    #[msg("Read cursor is past the last notification")]
+    InvalidReadCursor,
+    #[msg("User id doesn't exist or is your own")]
+    InvalidUser,
+    #[msg("User has blocked you")]
+    UserBlocked,
+    #[msg("A poll has 2 to 8 options")]
+    InvalidPollOptions,
+    #[msg("Poll must close at least a minute and at most a year in the future")]
+    InvalidPollClose,
+    #[msg("Option doesn't exist on this poll")]
+    InvalidVoteOption,
+    #[msg("Poll is closed")]
+    PollClosed,
+    #[msg("Poll is still open")]
+    PollStillOpen,
+    #[msg("Token weighted votes need a stake position of the poll mint")]
+    InvalidVoteStake,
+    #[msg("Token weighted polls need a close timestamp")]
+    WeightedPollNeverCloses,
+    #[msg("Names are 1 to 32 bytes")]
+    InvalidName,
+    #[msg("Visibility is either public or private")]
+    InvalidVisibility,
+    #[msg("Collection is full")]
+    CollectionFull,
+    #[msg("Post is already in this collection")]
+    AlreadyInCollection,
+    #[msg("Post is not in this collection")]
+    NotInCollection,
+    #[msg("Position is out of range")]
+    InvalidPosition,
+    #[msg("Remove all members before deleting the list")]
+    ListNotEmpty,
+    #[msg("Only public lists can be subscribed to")]
+    ListNotPublic,
+    #[msg("Account is protected, only approved followers can interact")]
+    NotAnApprovedFollower,
+    #[msg("Follow request is not pending")]
+    FollowRequestNotPending,
+    #[msg("Account did not pay for the follow request")]
+    InvalidFollowRequestPayer,
+    #[msg("Signer is not the owner or a valid session key for this action")]
+    NotAuthorized,
+    #[msg("Session key must expire in the future and within a week")]
+    InvalidSessionExpiry,
+    #[msg("Session key spend limit exceeded")]
+    SpendLimitExceeded,
+    #[msg("Profile has the maximum number of wallets")]
+    WalletsFull,
+    #[msg("Wallet is not a secondary wallet of this profile")]
+    WalletNotFound,
+    #[msg("Remove the other wallets before deleting the profile")]
+    WalletsRemaining,
+    #[msg("Guardians must be 1 to 5 other users or wallets, with a threshold up to their number")]
+    InvalidGuardians,
+    #[msg("Recovery delay must be between 1 and 30 days")]
+    InvalidRecoveryDelay,
+    #[msg("A recovery is in progress")]
+    RecoveryPending,
+    #[msg("Recovery is not pending")]
+    RecoveryNotPending,
+    #[msg("Recovery wasn't approved or executed in time")]
+    RecoveryExpired,
+    #[msg("Signer is not this guardian")]
+    NotAGuardian,
+    #[msg("Recovery is not approved, or the delay has not passed")]
+    RecoveryNotReady,
+    #[msg("Recovery wallet is already a wallet of this profile")]
+    InvalidRecoveryWallet,
+    #[msg("Attestation kind must be between 1 and 31")]
+    InvalidAttestationKind,
+    #[msg("Issuer is not allowed to issue this kind of attestation")]
+    IssuerNotAllowed,
+    #[msg("Attestation must expire in the future, or never")]
+    InvalidAttestationExpiry,
+    #[msg("User already has a valid attestation of this kind from another issuer")]
+    AttestationExists,
+    #[msg("Attestation was revoked by the admin")]
+    AttestationRevokedByAdmin,
+    #[msg("Post is not removed by moderation")]
+    PostNotRemoved,
+    #[msg("Account is already in the current layout")]
+    NothingToMigrate,
+    #[msg("Account the migration reads from is missing")]
+    MigrationAccountMissing,
+    #[msg("Reputation is too low to join groups")]
+    ReputationTooLow,
+    #[msg("Badge kind does not exist")]
+    InvalidBadgeKind,
+    #[msg("Milestone of this badge is not reached")]
+    BadgeNotEarned,
+    #[msg("Badge is already minted")]
+    BadgeAlreadyMinted,
+    #[msg("Group account doesn't match the group id")]
+    InvalidGroup,
+    #[msg("Referrer does not exist")]
+    InvalidReferrer,
+    #[msg("User was not referred by this user")]
+    NotTheReferrer,
+    #[msg("Referral reward is already paid")]
+    ReferralAlreadyPaid,
+    #[msg("Referred user has not reached the referral milestones")]
+    ReferralMilestoneNotReached,
+    #[msg("Reward epoch must be between an hour and a year")]
+    InvalidEpochLength,
+    #[msg("Reward epoch has not lasted long enough")]
+    EpochNotOver,
+    #[msg("Creator points of the current epoch are not passed")]
+    CreatorPointsMissing,
+    #[msg("Stake amount must be more than 0 and at most the staked tokens")]
+    InvalidStakeAmount,
+    #[msg("Stake cooldown must be between 0 and 30 days")]
+    InvalidStakeCooldown,
+    #[msg("Unstaked tokens are still in their cooldown")]
+    StakeCoolingDown,
+    #[msg("Staked tokens are locked until the polls they voted on close")]
+    StakeLockedByVote,
+    #[msg("Staked tokens have to be withdrawn before the profile is deleted")]
+    StakeRemaining,
+    #[msg("Subscription price must be more than 0")]
+    InvalidSubscriptionPrice,
+    #[msg("Subscription period must be whole days, between a day and a year")]
+    InvalidSubscriptionPeriod,
+    #[msg("Subscription approval must cover at least one period")]
+    InvalidSubscriptionPeriods,
+    #[msg("Users can't subscribe to themselves")]
+    CannotSubscribeToSelf,
+    #[msg("Subscription is already active")]
+    SubscriptionActive,
+    #[msg("Subscription is not active")]
+    SubscriptionNotActive,
+    #[msg("Account did not pay for the subscription thread")]
+    InvalidThreadPayer,
+    #[msg("Post is only open to subscribers of the author")]
+    SubscribersOnly,
+}
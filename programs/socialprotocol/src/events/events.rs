@@ -0,0 +1,246 @@
+use crate::*;
+
+// emitted when a post goes live, directly on submit or at the scheduled time
+#[event]
+pub struct PostPublished {
+    pub post: Pubkey, // post account
+    pub pid: u32,     // post id
+    pub uid: u32,     // user id of the author
+    pub gid: u32,     // group id
+    pub ts: i64,      // timestamp of publication
+}
+
+// emitted when a poll closes, with the final tallies
+#[event]
+pub struct PollClosed {
+    pub post: Pubkey,                     // post account of the poll
+    pub poll: Pubkey,                     // poll account
+    pub tallies: [u64; MAX_POLL_OPTIONS], // votes (or token weight) per option
+    pub voters: u32,                      // number of users that voted
+    pub ts: i64,                          // timestamp of closing
+}
+
+// emitted when a user asks to follow a protected account
+#[event]
+pub struct FollowRequested {
+    pub uid: u32,      // user id of the protected account
+    pub follower: u32, // user id of the requesting user
+    pub ts: i64,       // timestamp
+}
+
+// emitted when a protected account approves a follow request
+#[event]
+pub struct FollowApproved {
+    pub uid: u32,      // user id of the protected account
+    pub follower: u32, // user id of the approved follower
+    pub ts: i64,       // timestamp
+}
+
+// emitted when a protected account declines a follow request, or removes a follower
+#[event]
+pub struct FollowDeclined {
+    pub uid: u32,      // user id of the protected account
+    pub follower: u32, // user id of the declined follower
+    pub ts: i64,       // timestamp
+}
+
+// emitted when another wallet becomes the primary wallet of a profile
+#[event]
+pub struct PrimaryWalletRotated {
+    pub uid: u32,         // user id
+    pub previous: Pubkey, // previous primary wallet, now a secondary wallet
+    pub primary: Pubkey,  // new primary wallet
+    pub ts: i64,          // timestamp
+}
+
+// emitted when a profile names its guardians
+#[event]
+pub struct GuardiansSet {
+    pub uid: u32,      // user id
+    pub guardians: u8, // number of guardians
+    pub threshold: u8, // approvals needed to recover
+    pub delay: i64,    // seconds between approval and execution
+    pub ts: i64,       // timestamp
+}
+
+// emitted when a guardian asks to move a profile to a new wallet
+#[event]
+pub struct RecoveryInitiated {
+    pub uid: u32,          // user id
+    pub recovery: Pubkey,  // recovery account
+    pub wallet: Pubkey,    // new primary wallet
+    pub initiator: Pubkey, // guardian wallet
+    pub ts: i64,           // timestamp
+}
+
+// emitted for every guardian approval
+#[event]
+pub struct RecoveryApproved {
+    pub uid: u32,         // user id
+    pub recovery: Pubkey, // recovery account
+    pub guardian: u8,     // index of the guardian
+    pub signer: Pubkey,   // wallet that signed the approval
+    pub approvals: u8,    // approvals so far
+    pub ts: i64,          // timestamp
+}
+
+// emitted when the owner cancels a recovery
+#[event]
+pub struct RecoveryCancelled {
+    pub uid: u32,         // user id
+    pub recovery: Pubkey, // recovery account
+    pub ts: i64,          // timestamp
+}
+
+// emitted when a recovery moves the profile to the new wallet
+#[event]
+pub struct RecoveryExecuted {
+    pub uid: u32,         // user id
+    pub recovery: Pubkey, // recovery account
+    pub previous: Pubkey, // previous primary wallet
+    pub primary: Pubkey,  // new primary wallet
+    pub ts: i64,          // timestamp
+}
+
+// emitted when an issuer attests a user, also on renewal
+#[event]
+pub struct AttestationIssued {
+    pub uid: u32,       // user id
+    pub kind: u8,       // attestation kind
+    pub issuer: Pubkey, // wallet of the issuer
+    pub expiry: i64,    // timestamp the attestation ends (0 = never)
+    pub ts: i64,        // timestamp
+}
+
+// emitted when an attestation is revoked
+#[event]
+pub struct AttestationRevoked {
+    pub uid: u32,        // user id
+    pub kind: u8,        // attestation kind
+    pub issuer: Pubkey,  // wallet of the issuer
+    pub revoker: Pubkey, // issuer or admin that revoked it
+    pub ts: i64,         // timestamp
+}
+
+// emitted when the admin removes or restores a post
+#[event]
+pub struct PostModerated {
+    pub post: Pubkey,  // post account
+    pub pid: u32,      // post id
+    pub uid: u32,      // user id of the author
+    pub removed: bool, // removed, or restored after an appeal
+    pub ts: i64,       // timestamp
+}
+
+// emitted when a user is awarded a badge
+#[event]
+pub struct BadgeAwarded {
+    pub uid: u32, // user id
+    pub kind: u8, // badge kind
+    pub ts: i64,  // timestamp
+}
+
+// emitted when a user reaches the milestone of a badge, which award_badge can then award
+#[event]
+pub struct BadgeEarned {
+    pub uid: u32, // user id
+    pub kind: u8, // badge kind
+    pub ts: i64,  // timestamp
+}
+
+// emitted when a new user names a referrer
+#[event]
+pub struct UserReferred {
+    pub uid: u32,      // user id of the new user
+    pub referrer: u32, // user id of the referrer
+    pub ts: i64,       // timestamp
+}
+
+// emitted when a referrer is paid for a referred user
+#[event]
+pub struct ReferralRewarded {
+    pub referrer: u32, // user id of the referrer
+    pub referee: u32,  // user id of the referred user
+    pub amount: u64,   // tokens paid
+    pub ts: i64,       // timestamp
+}
+
+// emitted when a reward epoch ends
+#[event]
+pub struct EpochAdvanced {
+    pub epoch: u32,    // epoch that ended
+    pub points: u64,   // points earned by all creators
+    pub emission: u64, // tokens to be paid out for the epoch
+    pub ts: i64,       // timestamp
+}
+
+// emitted when a creator claims its share of an epoch
+#[event]
+pub struct RewardsClaimed {
+    pub uid: u32,    // user id of the creator
+    pub epoch: u32,  // epoch number
+    pub points: u64, // points of the creator
+    pub amount: u64, // tokens paid
+    pub ts: i64,     // timestamp
+}
+
+// emitted when a user stakes, unstakes or withdraws tokens
+#[event]
+pub struct StakeChanged {
+    pub uid: u32,       // user id
+    pub amount: u64,    // staked tokens
+    pub unstaking: u64, // tokens in cooldown
+    pub unlock_at: i64, // timestamp the unstaking tokens can be withdrawn
+    pub ts: i64,        // timestamp
+}
+
+// emitted when a user subscribes to a creator
+#[event]
+pub struct Subscribed {
+    pub creator: u32,    // user id of the creator
+    pub subscriber: u32, // user id of the subscriber
+    pub price: u64,      // tokens charged per period
+    pub period: i64,     // seconds per period
+    pub paid_until: i64, // timestamp the subscription is paid up to
+    pub ts: i64,         // timestamp
+}
+
+// emitted for every period a subscription is charged
+#[event]
+pub struct SubscriptionCharged {
+    pub creator: u32,    // user id of the creator
+    pub subscriber: u32, // user id of the subscriber
+    pub amount: u64,     // tokens charged
+    pub paid_until: i64, // timestamp the subscription is paid up to
+    pub ts: i64,         // timestamp
+}
+
+// emitted when a subscription can't be charged, or is cancelled by the subscriber
+#[event]
+pub struct SubscriptionEnded {
+    pub creator: u32,    // user id of the creator
+    pub subscriber: u32, // user id of the subscriber
+    pub status: u8,      // cancelled or lapsed
+    pub paid_until: i64, // timestamp the subscription stays active until
+    pub ts: i64,         // timestamp
+}
+
+// emitted for every direct message, the content is encrypted off-chain
+#[event]
+pub struct MessageSent {
+    pub conversation: Pubkey, // conversation account
+    pub message: Pubkey,      // message account
+    pub index: u32,           // index of the message in the conversation
+    pub uid: u32,             // user id of the sender
+    pub to: u32,              // user id of the recipient
+    pub ts: i64,              // timestamp
+}
+
+// emitted for every user mentioned in a post, when the post is published
+#[event]
+pub struct UserMentioned {
+    pub post: Pubkey,   // post account
+    pub pid: u32,       // post id
+    pub uid: u32,       // user id of the author
+    pub mentioned: u32, // user id of the mentioned user
+}
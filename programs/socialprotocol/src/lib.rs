@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::Discriminator;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use spl_token::solana_program::account_info::AccountInfo;
 use spl_token::solana_program::system_program;
@@ -8,10 +9,16 @@ use std::mem;
 declare_id!("BfZEDfZLyTkNgdotvwokkayzHxCYJZQqFQM8BMc9kSza");
 
 pub mod contexts;
+pub mod errors;
+pub mod events;
 pub mod states;
+pub mod utils;
 
 pub use contexts::*;
+pub use errors::*;
+pub use events::*;
 pub use states::*;
+pub use utils::*;
 
 #[program]
 pub mod socialprotocol {
@@ -24,19 +31,376 @@ pub mod socialprotocol {
         ctx.accounts.process(bump)
     }
 
-    pub fn setup_tags(ctx: Context<SetupTags>) -> Result<()> {
-        let bump = *ctx.bumps.get("tags").unwrap();
+    // the upgrade authority sets up the protocol config and becomes the admin
+    pub fn setup_config(
+        ctx: Context<SetupConfig>,
+        post_limit: ActionLimit,
+        reply_limit: ActionLimit,
+        like_limit: ActionLimit,
+        boost: u16,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("config").unwrap();
+        ctx.accounts
+            .process(post_limit, reply_limit, like_limit, boost, bump)
+    }
+
+    // admin can change the rate limits per action type
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        post_limit: ActionLimit,
+        reply_limit: ActionLimit,
+        like_limit: ActionLimit,
+        boost: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(post_limit, reply_limit, like_limit, boost)
+    }
+
+    // admin sets the reputation weights, and the thresholds for boosted limits, subsidies and groups
+    pub fn update_reputation(
+        ctx: Context<UpdateReputation>,
+        reputation: ReputationConfig,
+    ) -> Result<()> {
+        ctx.accounts.process(reputation)
+    }
+
+    // admin closes the former list of all tags, now that every tag has its own account
+    pub fn close_tags(ctx: Context<CloseTags>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // admin removes a post, or restores it after an appeal
+    pub fn moderate_post(ctx: Context<ModeratePost>, remove: bool) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts, remove)
+    }
+
+    // admin sets the referral reward and the milestones the referred user has to reach
+    pub fn update_referral(ctx: Context<UpdateReferral>, referral: ReferralConfig) -> Result<()> {
+        ctx.accounts.process(referral)
+    }
+
+    // admin creates the token account referral rewards are paid from, anyone can fund it
+    pub fn setup_referral_vault(ctx: Context<SetupReferralVault>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // the referrer claims the reward for a referred user that reached the milestones
+    pub fn claim_referral_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimReferralReward<'info>>,
+        _referee: u32,
+    ) -> Result<()> {
+        let vault_bump = *ctx.bumps.get("referral_vault").unwrap();
+        ctx.accounts.process(ctx.remaining_accounts, vault_bump)
+    }
+
+    // admin creates the creator reward pool and its vault, with the tokens paid per epoch
+    pub fn setup_rewards(
+        ctx: Context<SetupRewards>,
+        emission: u64,
+        epoch_length: i64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("reward_pool").unwrap();
+        ctx.accounts.process(emission, epoch_length, bump)
+    }
+
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount)
+    }
+
+    pub fn set_emission_rate(
+        ctx: Context<SetEmissionRate>,
+        emission: u64,
+        epoch_length: i64,
+    ) -> Result<()> {
+        ctx.accounts.process(emission, epoch_length)
+    }
+
+    // end the current reward epoch, anyone can call this once it lasted long enough
+    pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+        let bump = *ctx.bumps.get("reward_epoch").unwrap();
         ctx.accounts.process(bump)
     }
 
+    // a creator claims its share of an ended epoch, pro rata to its points
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, _epoch: u32) -> Result<()> {
+        let vault_bump = *ctx.bumps.get("reward_vault").unwrap();
+        ctx.accounts.process(vault_bump)
+    }
+
+    // admin creates the token account stakes are held in
+    pub fn setup_stake_vault(ctx: Context<SetupStakeVault>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // admin sets the unstake cooldown, and the stake needed for each perk
+    pub fn update_staking(ctx: Context<UpdateStaking>, staking: StakingConfig) -> Result<()> {
+        ctx.accounts.process(staking)
+    }
+
+    // stake tokens for higher rate limits, reply subsidies, more tags and poll weight
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        let bump = *ctx.bumps.get("stake_position").unwrap();
+        ctx.accounts.process(amount, bump)
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount)
+    }
+
+    // withdraw unstaked tokens once the cooldown passed
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        let vault_bump = *ctx.bumps.get("stake_vault").unwrap();
+        ctx.accounts.process(vault_bump)
+    }
+
+    // creator sets the price, token and period of its subscription
+    pub fn set_subscription_tier(
+        ctx: Context<SetSubscriptionTier>,
+        price: u64,
+        period: i64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("subscription_tier").unwrap();
+        ctx.accounts.process(price, period, bump)
+    }
+
+    // subscribe to a creator, the first period is paid right away and a clockwork thread
+    // charges the next periods through a delegate approval, fees fund the thread
+    pub fn subscribe(ctx: Context<Subscribe>, periods: u16, fees: u64) -> Result<()> {
+        let subscription_bump = *ctx.bumps.get("subscription").unwrap();
+        ctx.accounts.process(periods, fees, subscription_bump)
+    }
+
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // only active subscribers of the author can like, reply to and vote on the post
+    pub fn set_subscribers_only(
+        ctx: Context<SetSubscribersOnly>,
+        _shdw: Pubkey,
+        subscribers_only: bool,
+    ) -> Result<()> {
+        ctx.accounts.process(subscribers_only)
+    }
+
+    // admin allows an issuer to attest users, for the kinds in the mask
+    pub fn set_issuer(ctx: Context<SetIssuer>, address: Pubkey, kinds: u32) -> Result<()> {
+        let bump = *ctx.bumps.get("issuer").unwrap();
+        ctx.accounts.process(address, kinds, bump)
+    }
+
+    pub fn remove_issuer(ctx: Context<RemoveIssuer>, _address: Pubkey) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // attest a user, e.g. as verified, until the expiry (0 = never)
+    pub fn issue_attestation<'info>(
+        ctx: Context<'_, '_, '_, 'info, IssueAttestation<'info>>,
+        uid: u32,
+        kind: u8,
+        expiry: i64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("attestation").unwrap();
+        ctx.accounts
+            .process(ctx.remaining_accounts, uid, kind, expiry, bump)
+    }
+
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>, _uid: u32, _kind: u8) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // close an attestation, the admin uses this to undo its revocation
+    pub fn close_attestation(ctx: Context<CloseAttestation>, _uid: u32, _kind: u8) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // award a badge to a user that reached its milestone, anyone can call this
+    // badges are also awarded as the milestone is reached, when the badge account is passed
+    pub fn award_badge(ctx: Context<AwardBadge>, uid: u32, kind: u8) -> Result<()> {
+        let bump = *ctx.bumps.get("badge").unwrap();
+        ctx.accounts.process(uid, kind, bump)
+    }
+
+    // mint an awarded badge as a non-transferable token
+    pub fn mint_badge(ctx: Context<MintBadge>, kind: u8) -> Result<()> {
+        let mint_bump = *ctx.bumps.get("badge_mint").unwrap();
+        ctx.accounts.process(kind, mint_bump)
+    }
+
+    // initialize the trending leaderboard once
+    pub fn setup_trending(ctx: Context<SetupTrending>) -> Result<()> {
+        let bump = *ctx.bumps.get("trending").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    // read-only, simulate this instruction to get the top tags of today
+    pub fn get_trending_tags(ctx: Context<GetTrendingTags>, limit: u8) -> Result<Vec<TrendingTag>> {
+        ctx.accounts.process(limit)
+    }
+
+    // create a tag, anyone can add a tag that doesn't exist yet
+    pub fn create_tag<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateTag<'info>>,
+        name: String,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("tag").unwrap();
+        ctx.accounts.process(ctx.remaining_accounts, name, bump)
+    }
+
     // a user can add a profile, of which the content is stored on the Shadow Drive
-    pub fn create_user_profile(
-        ctx: Context<CreateUserProfile>,
+    pub fn create_user_profile<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateUserProfile<'info>>,
         shdw: Pubkey,
         amount: Option<u64>,
+        referrer: Option<u32>,
     ) -> Result<()> {
         let bump = *ctx.bumps.get("user_profile").unwrap();
-        ctx.accounts.process(shdw, amount, bump)
+        let wallet_bump = *ctx.bumps.get("wallet").unwrap();
+        ctx.accounts.process(
+            ctx.remaining_accounts,
+            shdw,
+            amount,
+            referrer,
+            bump,
+            wallet_bump,
+        )
+    }
+
+    // bring an account from before the upgrade to its current layout, anyone can call this
+    pub fn migrate_account<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateAccount<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    // move a profile seeded by its wallet to the user id seed, once per profile after the upgrade
+    pub fn migrate_user_profile(ctx: Context<MigrateUserProfile>, uid: u32) -> Result<()> {
+        let bump = *ctx.bumps.get("user_profile").unwrap();
+        let wallet_bump = *ctx.bumps.get("wallet").unwrap();
+        ctx.accounts.process(uid, bump, wallet_bump)
+    }
+
+    // add another wallet that can sign for the profile
+    pub fn add_wallet(ctx: Context<AddWallet>) -> Result<()> {
+        let bump = *ctx.bumps.get("wallet").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn remove_wallet(ctx: Context<RemoveWallet>, address: Pubkey) -> Result<()> {
+        ctx.accounts.process(address)
+    }
+
+    // make another wallet of the profile the primary wallet
+    pub fn rotate_primary_wallet(ctx: Context<RotatePrimaryWallet>, address: Pubkey) -> Result<()> {
+        ctx.accounts.process(address)
+    }
+
+    // name the guardians that can recover the profile, k of n approvals after a delay
+    pub fn set_guardians<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetGuardians<'info>>,
+        guardians: Vec<Guardian>,
+        threshold: u8,
+        delay: i64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("guardians").unwrap();
+        ctx.accounts
+            .process(ctx.remaining_accounts, guardians, threshold, delay, bump)
+    }
+
+    pub fn initiate_recovery<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitiateRecovery<'info>>,
+        guardian: u8,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("recovery").unwrap();
+        ctx.accounts
+            .process(ctx.remaining_accounts, guardian, wallet, bump)
+    }
+
+    pub fn approve_recovery<'info>(
+        ctx: Context<'_, '_, '_, 'info, ApproveRecovery<'info>>,
+        guardian: u8,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("approval").unwrap();
+        ctx.accounts.process(ctx.remaining_accounts, guardian, bump)
+    }
+
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        let bump = *ctx.bumps.get("wallet").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    // let an app sign for the profile with an ephemeral key, for the allowed actions until the expiry
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        session: Pubkey,
+        expiry: i64,
+        allowed: u32,
+        spend_limit: u64,
+        lamports: u64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("session_key").unwrap();
+        ctx.accounts
+            .process(session, expiry, allowed, spend_limit, lamports, bump)
+    }
+
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>, _session: Pubkey) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // a user can add an inbox, to receive likes, replies, follows and mentions
+    pub fn create_inbox(ctx: Context<CreateInbox>) -> Result<()> {
+        let bump = *ctx.bumps.get("inbox").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    // move the read cursor of the inbox to the given notification count
+    pub fn mark_read<'info>(
+        ctx: Context<'_, '_, '_, 'info, MarkRead<'info>>,
+        count: u32,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts, count)
+    }
+
+    // publish an X25519 public key, so other users can send encrypted messages
+    pub fn set_messaging_key(ctx: Context<SetMessagingKey>, key: [u8; 32]) -> Result<()> {
+        let bump = *ctx.bumps.get("messaging_key").unwrap();
+        ctx.accounts.process(key, bump)
+    }
+
+    // a blocked user can't open a conversation or send messages
+    pub fn block_user(ctx: Context<BlockUser>, address: u32) -> Result<()> {
+        let bump = *ctx.bumps.get("block").unwrap();
+        ctx.accounts.process(address, bump)
+    }
+
+    pub fn unblock_user(ctx: Context<UnblockUser>, _address: u32) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // open a direct message conversation with another user
+    pub fn open_conversation<'info>(
+        ctx: Context<'_, '_, '_, 'info, OpenConversation<'info>>,
+        address: u32,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("conversation").unwrap();
+        ctx.accounts.process(ctx.remaining_accounts, address, bump)
+    }
+
+    // send a direct message, the content is encrypted and stored on the Shadow Drive
+    pub fn send_message<'info>(
+        ctx: Context<'_, '_, '_, 'info, SendMessage<'info>>,
+        address: u32,
+        shdw: Pubkey,
+        nonce: [u8; 24],
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("message").unwrap();
+        ctx.accounts
+            .process(ctx.remaining_accounts, address, shdw, nonce, bump)
     }
 
     // create a group profile, of which the content is stored on the Shadow Drive
@@ -50,77 +414,321 @@ pub mod socialprotocol {
     }
 
     // user can join a group
-    pub fn join_group(ctx: Context<JoinGroup>, address: u32, amount: Option<u64>) -> Result<()> {
-        ctx.accounts.process(address, amount)
+    pub fn join_group<'info>(
+        ctx: Context<'_, '_, '_, 'info, JoinGroup<'info>>,
+        address: u32,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(ctx.remaining_accounts, address, amount)
     }
 
     // leave group
-    pub fn leave_group(ctx: Context<LeaveGroup>, address: u32, amount: Option<u64>) -> Result<()> {
-        ctx.accounts.process(address, amount)
+    pub fn leave_group<'info>(
+        ctx: Context<'_, '_, '_, 'info, LeaveGroup<'info>>,
+        address: u32,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(ctx.remaining_accounts, address, amount)
     }
 
     // user can follow another user
-    pub fn follow_user(ctx: Context<FollowUser>, address: u32, amount: Option<u64>) -> Result<()> {
-        ctx.accounts.process(address, amount)
+    pub fn follow_user<'info>(
+        ctx: Context<'_, '_, '_, 'info, FollowUser<'info>>,
+        address: u32,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(ctx.remaining_accounts, address, amount)
+    }
+
+    // only approved followers can like and reply to posts of a protected account
+    pub fn set_protected(ctx: Context<SetProtected>, protected: bool) -> Result<()> {
+        let bump = *ctx.bumps.get("profile_settings").unwrap();
+        ctx.accounts.process(protected, bump)
+    }
+
+    // a protected account approves follow requests
+    pub fn approve_follow_request(
+        ctx: Context<ApproveFollowRequest>,
+        _follower: u32,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // decline a pending follow request, or remove an approved follower
+    pub fn decline_follow_request(
+        ctx: Context<DeclineFollowRequest>,
+        _follower: u32,
+    ) -> Result<()> {
+        ctx.accounts.process()
     }
 
     // unfollow another user
-    pub fn unfollow_user(
-        ctx: Context<UnfollowUser>,
+    pub fn unfollow_user<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnfollowUser<'info>>,
         address: u32,
         amount: Option<u64>,
     ) -> Result<()> {
-        ctx.accounts.process(address, amount)
+        ctx.accounts
+            .process(ctx.remaining_accounts, address, amount)
+    }
+
+    // a user can add named lists of users, public or private
+    pub fn create_list<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateList<'info>>,
+        name: String,
+        visibility: u8,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("list").unwrap();
+        ctx.accounts
+            .process(ctx.remaining_accounts, name, visibility, bump)
     }
 
-    pub fn submit_post(
-        ctx: Context<SubmitPost>,
+    pub fn add_to_list<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddToList<'info>>,
+        _name: String,
+        address: u32,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("list_member").unwrap();
+        ctx.accounts.process(ctx.remaining_accounts, address, bump)
+    }
+
+    pub fn remove_from_list<'info>(
+        ctx: Context<'_, '_, '_, 'info, RemoveFromList<'info>>,
+        _name: String,
+        _address: u32,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    // a list can only be deleted once all members are removed
+    pub fn delete_list<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeleteList<'info>>,
+        _name: String,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    // subscribe to a public list of another user
+    pub fn subscribe_list<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubscribeList<'info>>,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("list_subscription").unwrap();
+        ctx.accounts.process(ctx.remaining_accounts, bump)
+    }
+
+    pub fn unsubscribe_list<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnsubscribeList<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    // submit a post, optionally deleted by a clockwork thread at the expiry timestamp
+    pub fn submit_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubmitPost<'info>>,
         group_id: u32,
         shdw: Pubkey,
-        tag_name: String,
+        tag_names: Vec<String>,
+        mentions: Vec<u32>,
         amount: Option<u64>,
-        schedule: String,
+        expiry: Option<i64>,
+    ) -> Result<()> {
+        let post_bump = *ctx.bumps.get("post").unwrap();
+        let likes_bump = *ctx.bumps.get("likes").unwrap();
+        let rate_limit_bump = *ctx.bumps.get("rate_limit").unwrap();
+        ctx.accounts.process(
+            ctx.remaining_accounts,
+            group_id,
+            shdw,
+            tag_names,
+            mentions,
+            amount,
+            post_bump,
+            likes_bump,
+            rate_limit_bump,
+            expiry,
+        )
+    }
+
+    // schedule a post, a clockwork thread publishes it at the publish_at timestamp
+    pub fn schedule_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, SchedulePost<'info>>,
+        group_id: u32,
+        shdw: Pubkey,
+        tag_names: Vec<String>,
+        mentions: Vec<u32>,
+        publish_at: i64,
+    ) -> Result<()> {
+        let post_bump = *ctx.bumps.get("post").unwrap();
+        let likes_bump = *ctx.bumps.get("likes").unwrap();
+        let rate_limit_bump = *ctx.bumps.get("rate_limit").unwrap();
+        ctx.accounts.process(
+            ctx.remaining_accounts,
+            group_id,
+            shdw,
+            tag_names,
+            mentions,
+            publish_at,
+            post_bump,
+            likes_bump,
+            rate_limit_bump,
+        )
+    }
+
+    // submit a poll, optionally closed by a clockwork thread at the close timestamp
+    pub fn submit_poll<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubmitPoll<'info>>,
+        group_id: u32,
+        shdw: Pubkey,
+        options: u8,
+        closes_at: Option<i64>,
+        token_weighted: bool,
     ) -> Result<()> {
         let post_bump = *ctx.bumps.get("post").unwrap();
         let likes_bump = *ctx.bumps.get("likes").unwrap();
+        let poll_bump = *ctx.bumps.get("poll").unwrap();
+        let rate_limit_bump = *ctx.bumps.get("rate_limit").unwrap();
         ctx.accounts.process(
-            group_id, shdw, tag_name, amount, post_bump, likes_bump, schedule,
+            ctx.remaining_accounts,
+            group_id,
+            shdw,
+            options,
+            closes_at,
+            token_weighted,
+            post_bump,
+            likes_bump,
+            poll_bump,
+            rate_limit_bump,
         )
     }
 
+    // vote on an option of a poll, once per user
+    pub fn vote_poll<'info>(
+        ctx: Context<'_, '_, '_, 'info, VotePoll<'info>>,
+        option: u8,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("poll_vote").unwrap();
+        ctx.accounts.process(ctx.remaining_accounts, option, bump)
+    }
+
+    // close a poll of which the post no longer exists, rent goes to the poll payer
+    pub fn sweep_orphaned_poll(ctx: Context<SweepOrphanedPoll>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    // save a post
+    pub fn bookmark_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, BookmarkPost<'info>>,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("bookmark").unwrap();
+        ctx.accounts.process(ctx.remaining_accounts, bump)
+    }
+
+    pub fn remove_bookmark<'info>(
+        ctx: Context<'_, '_, '_, 'info, RemoveBookmark<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    // a user can add named collections of posts, public or private
+    pub fn create_collection<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCollection<'info>>,
+        name: String,
+        visibility: u8,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("collection").unwrap();
+        ctx.accounts
+            .process(ctx.remaining_accounts, name, visibility, bump)
+    }
+
+    pub fn add_to_collection<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddToCollection<'info>>,
+        _name: String,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn remove_from_collection<'info>(
+        ctx: Context<'_, '_, '_, 'info, RemoveFromCollection<'info>>,
+        _name: String,
+        post: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts, post)
+    }
+
+    // move a post within a collection
+    pub fn reorder_collection<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReorderCollection<'info>>,
+        _name: String,
+        from: u16,
+        to: u16,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts, from, to)
+    }
+
+    pub fn delete_collection<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeleteCollection<'info>>,
+        _name: String,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
     // like a post
-    pub fn like_post(ctx: Context<LikePost>, amount: Option<u64>) -> Result<()> {
-        ctx.accounts.process(amount)
+    pub fn like_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, LikePost<'info>>,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        let rate_limit_bump = *ctx.bumps.get("rate_limit").unwrap();
+        ctx.accounts
+            .process(ctx.remaining_accounts, amount, rate_limit_bump)
     }
 
-    pub fn submit_reply(
-        ctx: Context<SubmitReply>,
+    pub fn submit_reply<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubmitReply<'info>>,
         post_id: u32,
         shdw: Pubkey,
         amount: Option<u64>,
     ) -> Result<()> {
         let bump = *ctx.bumps.get("reply").unwrap();
-        ctx.accounts.process(post_id, shdw, amount, bump)
+        let rate_limit_bump = *ctx.bumps.get("rate_limit").unwrap();
+        ctx.accounts.process(
+            ctx.remaining_accounts,
+            post_id,
+            shdw,
+            amount,
+            bump,
+            rate_limit_bump,
+        )
     }
 
     // delete a post
-    pub fn delete_post(
-        ctx: Context<DeletePost>,
+    pub fn delete_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeletePost<'info>>,
         _group_id: u32,
-        _shdw: Pubkey,
+        shdw: Pubkey,
         amount: Option<u64>,
     ) -> Result<()> {
-        ctx.accounts.process(amount)
+        ctx.accounts.process(ctx.remaining_accounts, shdw, amount)
     }
 
     // delete a reply
-    pub fn delete_reply(
-        ctx: Context<DeleteReply>,
+    pub fn delete_reply<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeleteReply<'info>>,
         _post_id: u32,
         _shdw: Pubkey,
         amount: Option<u64>,
     ) -> Result<()> {
-        ctx.accounts.process(amount)
+        ctx.accounts.process(ctx.remaining_accounts, amount)
+    }
+
+    // close a reply of which the post no longer exists, rent goes to the reply payer
+    pub fn sweep_orphaned_reply<'info>(
+        ctx: Context<'_, '_, '_, 'info, SweepOrphanedReply<'info>>,
+        _shdw: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
     }
 
     // delete user profile
@@ -159,7 +767,34 @@ pub mod socialprotocol {
         ctx.accounts.process(amount)
     }
 
-    pub fn clockwork_delete_post(ctx: Context<ClockworkDeletePost>) -> Result<()> {
-        ctx.accounts.process()
+    // called by the expiry thread of a post to mark it expired, and by anyone after the
+    // expiry to close the post and its thread
+    pub fn clockwork_delete_post<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClockworkDeletePost<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    // called by the poll thread at the close timestamp, or by anyone after it
+    pub fn close_poll(ctx: Context<ClosePoll>) -> Result<()> {
+        let post_bump = *ctx.bumps.get("post").unwrap();
+        ctx.accounts.process(post_bump)
+    }
+
+    // called by the subscription thread every day, charges when a period ends
+    // called by anyone once the subscription ended, deletes the thread
+    pub fn charge_subscription(ctx: Context<ChargeSubscription>) -> Result<()> {
+        let delegate_bump = *ctx.bumps.get("delegate").unwrap();
+        ctx.accounts.process(delegate_bump)
+    }
+
+    // close a thread of a post once it ran, the thread balance goes to the post payer
+    pub fn close_post_thread(ctx: Context<ClosePostThread>, id: String) -> Result<()> {
+        ctx.accounts.process(id)
+    }
+
+    // called by the publish thread of a scheduled post, or by anyone after the scheduled time
+    pub fn publish_post<'info>(ctx: Context<'_, '_, '_, 'info, PublishPost<'info>>) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
     }
 }
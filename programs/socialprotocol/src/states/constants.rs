@@ -0,0 +1,129 @@
+// a post expiry has to be at least an hour and at most a year in the future
+pub const MIN_POST_LIFETIME: i64 = 60 * 60;
+pub const MAX_POST_LIFETIME: i64 = 365 * 24 * 60 * 60;
+
+// tag names are 1 to 32 characters of a-z, 0-9 and _
+pub const MAX_TAG_LENGTH: usize = 32;
+
+// a post carries at most this many tags and mentions, stakers can add more tags
+pub const MAX_POST_TAGS: usize = 5;
+pub const MAX_STAKED_POST_TAGS: usize = 10;
+pub const MAX_POST_MENTIONS: usize = 5;
+
+// the trending leaderboard keeps the top tags, every post adds to the
+// activity score of its tags and the score halves every day
+pub const TRENDING_TAGS: usize = 10;
+pub const TRENDING_SCORE_PER_POST: u64 = 1_000_000;
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+// an inbox keeps the latest notifications, older ones are overwritten
+pub const INBOX_CAPACITY: usize = 16;
+
+// notification kinds
+pub const NOTIFICATION_LIKE: u8 = 1;
+pub const NOTIFICATION_REPLY: u8 = 2;
+pub const NOTIFICATION_FOLLOW: u8 = 3;
+pub const NOTIFICATION_MENTION: u8 = 4;
+pub const NOTIFICATION_MESSAGE: u8 = 5;
+
+// a poll has 2 to 8 options, and closes at least a minute and at most a year in the future
+pub const MIN_POLL_OPTIONS: u8 = 2;
+pub const MAX_POLL_OPTIONS: usize = 8;
+pub const MIN_POLL_DURATION: i64 = 60;
+pub const MAX_POLL_DURATION: i64 = 365 * 24 * 60 * 60;
+
+// names of collections and lists are 1 to 32 bytes, they are part of the seeds
+pub const MAX_NAME_LENGTH: usize = 32;
+
+// a collection holds at most this many posts
+pub const MAX_COLLECTION_POSTS: usize = 100;
+
+// visibility of collections and lists, account data is public either way,
+// private ones are only hidden by clients
+pub const VISIBILITY_PUBLIC: u8 = 1;
+pub const VISIBILITY_PRIVATE: u8 = 2;
+
+// follow request status
+pub const FOLLOW_REQUEST_PENDING: u8 = 1;
+pub const FOLLOW_REQUEST_APPROVED: u8 = 2;
+
+// actions a session key can be allowed to do, as bits of a mask
+pub const SESSION_POST: u32 = 1 << 0;
+pub const SESSION_REPLY: u32 = 1 << 1;
+pub const SESSION_LIKE: u32 = 1 << 2;
+pub const SESSION_FOLLOW: u32 = 1 << 3;
+pub const SESSION_GROUP: u32 = 1 << 4;
+pub const SESSION_VOTE: u32 = 1 << 5;
+pub const SESSION_CURATE: u32 = 1 << 6;
+pub const SESSION_MESSAGE: u32 = 1 << 7;
+pub const SESSION_INBOX: u32 = 1 << 8;
+pub const SESSION_DELETE: u32 = 1 << 9;
+
+// a session key is valid for at most a week
+pub const MAX_SESSION_DURATION: i64 = 7 * 24 * 60 * 60;
+
+// a profile has a primary wallet and at most this many other wallets
+pub const MAX_WALLETS: usize = 3;
+
+// a profile names at most this many guardians, a recovery waits 1 to 30 days once approved
+pub const MAX_GUARDIANS: usize = 5;
+pub const MIN_RECOVERY_DELAY: i64 = 24 * 60 * 60;
+pub const MAX_RECOVERY_DELAY: i64 = 30 * 24 * 60 * 60;
+
+// guardians have 7 days to approve a recovery, after that anyone can cancel it
+pub const RECOVERY_APPROVAL_WINDOW: i64 = 7 * 24 * 60 * 60;
+
+// recovery status
+pub const RECOVERY_PENDING: u8 = 1;
+pub const RECOVERY_CANCELLED: u8 = 2;
+pub const RECOVERY_EXECUTED: u8 = 3;
+
+// attestation kinds, issuers are allowed kinds as bits of a mask, so up to 31 kinds
+pub const ATTESTATION_VERIFIED: u8 = 1;
+pub const ATTESTATION_OFFICIAL_ORG: u8 = 2;
+pub const ATTESTATION_TEAM_MEMBER: u8 = 3;
+pub const MAX_ATTESTATION_KIND: u8 = 31;
+
+// badge kinds, each awarded once per user when its milestone is reached
+pub const BADGE_FIRST_POST: u8 = 1;
+pub const BADGE_HUNDRED_LIKES: u8 = 2;
+pub const BADGE_EARLY_USER: u8 = 3;
+pub const BADGE_GROUP_FOUNDER: u8 = 4;
+
+// milestones of the badges
+pub const BADGE_LIKES: u32 = 100;
+pub const BADGE_EARLY_USERS: u32 = 1000;
+pub const BADGE_FOUNDING_MEMBERS: u16 = 10;
+
+// reward points a creator earns per like and per reply on its posts
+pub const REWARD_POINTS_LIKE: u64 = 1;
+pub const REWARD_POINTS_REPLY: u64 = 2;
+
+// epoch recorded on a like or reply that earned no reward points
+pub const NO_REWARD_EPOCH: u32 = u32::MAX;
+
+// a reward epoch lasts at least an hour and at most a year
+pub const MIN_REWARD_EPOCH: i64 = 60 * 60;
+pub const MAX_REWARD_EPOCH: i64 = 365 * 24 * 60 * 60;
+
+// unstaked tokens can be withdrawn after a cooldown of at most 30 days
+pub const MAX_STAKE_COOLDOWN: i64 = 30 * 24 * 60 * 60;
+
+// subscription periods are whole days, from a day to a year, the thread checks daily if a charge is due
+pub const SUBSCRIPTION_DAY: i64 = 24 * 60 * 60;
+pub const MAX_SUBSCRIPTION_PERIOD: i64 = 365 * SUBSCRIPTION_DAY;
+
+// subscription status
+pub const SUBSCRIPTION_ACTIVE: u8 = 1;
+pub const SUBSCRIPTION_CANCELLED: u8 = 2;
+pub const SUBSCRIPTION_LAPSED: u8 = 3;
+
+// post status (st)
+pub const POST_STATUS_ACTIVE: u8 = 1;
+pub const POST_STATUS_SCHEDULED: u8 = 2;
+pub const POST_STATUS_REMOVED: u8 = 3;
+pub const POST_STATUS_EXPIRED: u8 = 4;
+
+// a scheduled post goes live at least a minute and at most a year in the future
+pub const MIN_SCHEDULE_DELAY: i64 = 60;
+pub const MAX_SCHEDULE_DELAY: i64 = 365 * 24 * 60 * 60;
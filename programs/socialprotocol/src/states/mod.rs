@@ -1,3 +1,5 @@
+pub mod constants;
 pub mod states;
 
+pub use constants::*;
 pub use states::*;
@@ -1,20 +1,515 @@
 use crate::*;
+use anchor_lang::solana_program::program_option::COption;
 
 #[account]
 pub struct Post {
+    pub ts: i64,                            // 8 byte - timestamp
+    pub uid: u32,                           // 4 byte - user id (max 4,294,967,295)
+    pub pid: u32,                           // 4 byte - post id (max 4,294,967,295)
+    pub gid: u32,                           // 4 byte - group id (max 4,294,967,295)
+    pub tags: [u32; MAX_STAKED_POST_TAGS],  // 40 byte - tag ids (0 = empty)
+    pub mentions: [u32; MAX_POST_MENTIONS], // 20 byte - mentioned user ids (0 = empty)
+    pub expiry: i64,                        // 8 byte - timestamp of deletion (0 = never)
+    pub payer: Pubkey,                      // 32 byte - paid the rent, refunded on close
+    pub st: u8,                             // 1 byte - status (default = 1, max 255)
+    pub bump: u8,                           // 1 byte - bump
+    pub subscribers_only: bool,             // 1 byte - only subscribers of the author interact
+}
+
+// layout of a post before tags, mentions and expiry, see migrate_account
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyPost {
     pub ts: i64,  // 8 byte - timestamp
     pub uid: u32, // 4 byte - user id (max 4,294,967,295)
     pub pid: u32, // 4 byte - post id (max 4,294,967,295)
     pub gid: u32, // 4 byte - group id (max 4,294,967,295)
-    pub tid: u16, // 2 byte - tag id (default = 0, max 65,535)
+    pub tid: u16, // 2 byte - index in the former tags list
     pub st: u8,   // 1 byte - status (default = 1, max 255)
     pub bump: u8, // 1 byte - bump
 }
 
+impl LegacyPost {
+    // the former tags list is gone, so the post starts without tags
+    pub fn migrate(self, payer: Pubkey) -> Post {
+        Post {
+            ts: self.ts,
+            uid: self.uid,
+            pid: self.pid,
+            gid: self.gid,
+            tags: [0; MAX_STAKED_POST_TAGS],
+            mentions: [0; MAX_POST_MENTIONS],
+            expiry: 0,
+            payer,
+            st: self.st,
+            bump: self.bump,
+            subscribers_only: false,
+        }
+    }
+}
+
+impl Post {
+    // a post with an expiry is closed once the expiry passed
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expiry != 0 && now >= self.expiry
+    }
+
+    // published or scheduled, and not taken down since
+    pub fn is_live(&self) -> bool {
+        self.st == POST_STATUS_ACTIVE || self.st == POST_STATUS_SCHEDULED
+    }
+
+    // subscriber-only posts are open to the author and active subscribers, the subscription
+    // of the user is passed in the remaining accounts
+    pub fn check_subscriber(&self, accounts: &[AccountInfo], uid: u32, now: i64) -> Result<()> {
+        require!(
+            !self.subscribers_only || Subscription::is_subscribed(accounts, self.uid, uid, now)?,
+            SplingError::SubscribersOnly
+        );
+        Ok(())
+    }
+
+    // attach tags to the post, every tag also gets an entry in its post index
+    pub fn add_tags<'info>(
+        &mut self,
+        key: Pubkey,
+        tag_names: &[String],
+        max_tags: usize,
+        accounts: &[AccountInfo<'info>],
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        require!(tag_names.len() <= max_tags, SplingError::TooManyTags);
+
+        for (slot, tag_name) in tag_names.iter().enumerate() {
+            // the tag account is looked up by its name in the remaining accounts
+            let mut tag = Tag::load(accounts, tag_name)?;
+            require!(!self.tags.contains(&tag.id), SplingError::DuplicateTag);
+            self.tags[slot] = tag.id;
+
+            // index entry n of a tag is seeded by the tag and its index counter
+            let index = tag.next.to_le_bytes();
+            let (tag_post_key, bump) = Pubkey::find_program_address(
+                &[b"tag_post", tag.key().as_ref(), &index],
+                &crate::ID,
+            );
+            let tag_post =
+                find_account(accounts, &tag_post_key).ok_or(SplingError::TagIndexNotFound)?;
+            create_account(
+                payer,
+                tag_post,
+                system_program,
+                8 + mem::size_of::<TagPost>(),
+                &[b"tag_post", tag.key().as_ref(), &index, &[bump]],
+            )?;
+            let mut data = tag_post.try_borrow_mut_data()?;
+            TagPost {
+                tag: tag.id,
+                post: key,
+                ts: self.ts,
+                bump,
+            }
+            .try_serialize(&mut &mut data[..])?;
+
+            tag.posts += 1;
+            tag.next += 1;
+
+            tag.exit(&crate::ID)?;
+        }
+        Ok(())
+    }
+
+    // count the tags of a live post towards their activity score and the trending leaderboard
+    pub fn credit_tags(
+        &self,
+        trending: &mut Trending,
+        accounts: &[AccountInfo],
+        now: i64,
+    ) -> Result<()> {
+        let day = Tag::day(now);
+        for id in self.tags.iter().filter(|id| **id != 0) {
+            let mut tag = Tag::find(accounts, *id)?;
+            tag.record_activity(day);
+            trending.update(tag.key(), tag.id, tag.score, day);
+            tag.exit(&crate::ID)?;
+        }
+        Ok(())
+    }
+
+    // count a post that is restored after moderation towards its tags again
+    pub fn restore_tags(
+        &self,
+        trending: &mut Trending,
+        accounts: &[AccountInfo],
+        now: i64,
+    ) -> Result<()> {
+        for id in self.tags.iter().filter(|id| **id != 0) {
+            let mut tag = Tag::find(accounts, *id)?;
+            tag.posts += 1;
+            tag.exit(&crate::ID)?;
+        }
+        self.credit_tags(trending, accounts, now)
+    }
+
+    // take a post that is deleted or removed by moderation off the post count and the activity
+    // score of its tags, a removed post was taken off already
+    pub fn debit_tags(
+        &self,
+        trending: &mut Trending,
+        accounts: &[AccountInfo],
+        now: i64,
+    ) -> Result<()> {
+        if !self.is_live() {
+            return Ok(());
+        }
+
+        let day = Tag::day(now);
+        for id in self.tags.iter().filter(|id| **id != 0) {
+            let mut tag = Tag::find(accounts, *id)?;
+            tag.posts = tag.posts.saturating_sub(1);
+
+            // a scheduled post is only credited once it is published
+            if self.st != POST_STATUS_SCHEDULED {
+                tag.remove_activity(Tag::day(self.ts), day);
+                trending.update(tag.key(), tag.id, tag.score, day);
+            }
+            tag.exit(&crate::ID)?;
+        }
+        Ok(())
+    }
+
+    // detach the tags of a deleted post, its post index entries are closed
+    // tag accounts and their post index entries are passed in the remaining accounts
+    pub fn remove_tags<'info>(
+        &self,
+        key: Pubkey,
+        trending: &mut Trending,
+        accounts: &[AccountInfo<'info>],
+        payer: &AccountInfo<'info>,
+        now: i64,
+    ) -> Result<()> {
+        self.debit_tags(trending, accounts, now)?;
+
+        // the payer of the post paid for the index entries too
+        for id in self.tags.iter().filter(|id| **id != 0) {
+            let tag_post = TagPost::find(accounts, *id, key)?;
+            tag_post.close(payer.clone())?;
+        }
+        Ok(())
+    }
+
+    // store the mentioned user ids, duplicates and unknown users are rejected
+    pub fn add_mentions(&mut self, mentions: &[u32], users: u32) -> Result<()> {
+        require!(
+            mentions.len() <= MAX_POST_MENTIONS,
+            SplingError::TooManyMentions
+        );
+
+        for (slot, uid) in mentions.iter().enumerate() {
+            require!(
+                *uid != 0 && *uid <= users && !self.mentions.contains(uid),
+                SplingError::InvalidMention
+            );
+            self.mentions[slot] = *uid;
+        }
+        Ok(())
+    }
+
+    // let the clients of mentioned users know, once the post is published
+    // inboxes of mentioned users are passed in the remaining accounts
+    pub fn emit_mentions(&self, key: Pubkey, accounts: &[AccountInfo]) -> Result<()> {
+        for uid in self.mentions.iter().filter(|uid| **uid != 0) {
+            emit!(UserMentioned {
+                post: key,
+                pid: self.pid,
+                uid: self.uid,
+                mentioned: *uid,
+            });
+            Inbox::notify(
+                accounts,
+                *uid,
+                Notification {
+                    kind: NOTIFICATION_MENTION,
+                    uid: self.uid,
+                    key,
+                    ts: self.ts,
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// one account per tag, seeded by the normalized tag name
 #[account]
-pub struct Tags {
-    pub taglist: Vec<String>, //  list with hashtags
-    pub bump: u8,             //  1 byte - bump
+pub struct Tag {
+    pub id: u32,        // 4 byte - tag id (stable, assigned at creation)
+    pub name: String,   // normalized tag name (max 32 characters)
+    pub posts: u32,     // 4 byte - number of live posts with this tag
+    pub day: u32,       // 4 byte - day (unix time / 86400) of the last post
+    pub day_posts: u32, // 4 byte - posts with this tag on that day
+    pub score: u64,     // 8 byte - activity score, halves every day
+    pub uid: u32,       // 4 byte - user id of the creator
+    pub ts: i64,        // 8 byte - timestamp
+    pub bump: u8,       // 1 byte - bump
+    pub next: u32,      // 4 byte - index of the next entry in the post index
+}
+
+impl Tag {
+    // lowercase a tag name and check its length and charset
+    pub fn normalize(name: &str) -> Result<String> {
+        let name = name.to_lowercase();
+        require!(
+            !name.is_empty()
+                && name.len() <= MAX_TAG_LENGTH
+                && name
+                    .bytes()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'_'),
+            SplingError::InvalidTagName
+        );
+        Ok(name)
+    }
+
+    pub fn day(unix_timestamp: i64) -> u32 {
+        unix_timestamp.div_euclid(SECONDS_PER_DAY) as u32
+    }
+
+    // decay the score to the given day, then add the activity of a post
+    pub fn record_activity(&mut self, day: u32) {
+        if day != self.day {
+            self.score = decay(self.score, self.day, day);
+            self.day = day;
+            self.day_posts = 0;
+        }
+        self.day_posts += 1;
+        self.score = self.score.saturating_add(TRENDING_SCORE_PER_POST);
+    }
+
+    // take the activity of a deleted post back out, as far as it has decayed by now
+    pub fn remove_activity(&mut self, post_day: u32, day: u32) {
+        if day != self.day {
+            self.score = decay(self.score, self.day, day);
+            self.day = day;
+            self.day_posts = 0;
+        } else if post_day == day {
+            self.day_posts = self.day_posts.saturating_sub(1);
+        }
+        self.score = self
+            .score
+            .saturating_sub(decay(TRENDING_SCORE_PER_POST, post_day, day));
+    }
+
+    pub fn pubkey(name: &str) -> Pubkey {
+        Pubkey::find_program_address(&[b"tag", name.as_bytes()], &crate::ID).0
+    }
+
+    // resolve a tag name to its tag account, passed in the remaining accounts
+    pub fn load<'info>(accounts: &[AccountInfo<'info>], name: &str) -> Result<Account<'info, Tag>> {
+        let name = Tag::normalize(name)?;
+        let info = find_account(accounts, &Tag::pubkey(&name)).ok_or(SplingError::TagNotFound)?;
+        require!(info.is_writable, SplingError::TagNotFound);
+        Account::try_from(info)
+    }
+
+    // find a tag by its id in the remaining accounts, when its name is not known
+    pub fn find<'info>(accounts: &[AccountInfo<'info>], id: u32) -> Result<Account<'info, Tag>> {
+        accounts
+            .iter()
+            .filter(|info| info.is_writable && info.owner == &crate::ID)
+            .find_map(|info| {
+                Account::<Tag>::try_from(info)
+                    .ok()
+                    .filter(|tag| tag.id == id)
+            })
+            .ok_or_else(|| SplingError::TagNotFound.into())
+    }
+}
+
+// leaderboard of the most active tags, sorted by score
+#[account]
+pub struct Trending {
+    pub tags: Vec<TrendingTag>, // top tags (max 10)
+    pub bump: u8,               // 1 byte - bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TrendingTag {
+    pub tag: Pubkey, // 32 byte - tag account
+    pub id: u32,     // 4 byte - tag id
+    pub score: u64,  // 8 byte - activity score on the given day
+    pub day: u32,    // 4 byte - day the score was last updated
+}
+
+impl Trending {
+    // put a tag with its new score on the leaderboard, if it makes the cut
+    pub fn update(&mut self, tag: Pubkey, id: u32, score: u64, day: u32) {
+        // bring every entry to the same day, so the scores compare
+        self.tags = self.top(day);
+
+        match self.tags.iter_mut().find(|entry| entry.tag == tag) {
+            Some(entry) => entry.score = score,
+            None => self.tags.push(TrendingTag {
+                tag,
+                id,
+                score,
+                day,
+            }),
+        }
+
+        self.tags.sort_by_key(|t| std::cmp::Reverse(t.score));
+        self.tags.truncate(TRENDING_TAGS);
+    }
+
+    // the leaderboard with every score decayed to the given day
+    pub fn top(&self, day: u32) -> Vec<TrendingTag> {
+        self.tags
+            .iter()
+            .map(|entry| TrendingTag {
+                score: decay(entry.score, entry.day, day),
+                day,
+                ..*entry
+            })
+            .filter(|entry| entry.score > 0)
+            .collect()
+    }
+}
+
+// an activity score halves for every day that passed
+pub fn decay(score: u64, from_day: u32, to_day: u32) -> u64 {
+    score
+        .checked_shr(to_day.saturating_sub(from_day))
+        .unwrap_or(0)
+}
+
+// post index of a tag, one account per tagged post
+#[account]
+pub struct TagPost {
+    pub tag: u32,     // 4 byte - tag id
+    pub post: Pubkey, // 32 byte - tagged post account
+    pub ts: i64,      // 8 byte - timestamp
+    pub bump: u8,     // 1 byte - bump
+}
+
+impl TagPost {
+    // only the program writes index entries, so one with this tag and post is the right one
+    pub fn find<'info>(
+        accounts: &[AccountInfo<'info>],
+        tag: u32,
+        post: Pubkey,
+    ) -> Result<Account<'info, TagPost>> {
+        accounts
+            .iter()
+            .filter(|info| info.is_writable && info.owner == &crate::ID)
+            .find_map(|info| {
+                Account::<TagPost>::try_from(info)
+                    .ok()
+                    .filter(|entry| entry.tag == tag && entry.post == post)
+            })
+            .ok_or_else(|| SplingError::TagIndexNotFound.into())
+    }
+}
+
+// a poll is a post with options, the option texts are stored with the post content
+#[account]
+pub struct Poll {
+    pub post: Pubkey,                     // 32 byte - post account of the poll
+    pub options: u8,                      // 1 byte - number of options
+    pub tallies: [u64; MAX_POLL_OPTIONS], // 64 byte - votes (or token weight) per option
+    pub voters: u32,                      // 4 byte - number of users that voted
+    pub closes_at: i64,                   // 8 byte - timestamp of closing (0 = never)
+    pub mint: Pubkey,                     // 32 byte - token weighted votes (default = 1 per user)
+    pub payer: Pubkey,                    // 32 byte - paid the rent, refunded on close
+    pub closed: bool,                     // 1 byte - no more votes
+    pub bump: u8,                         // 1 byte - bump
+}
+
+impl Poll {
+    pub fn is_open(&self, now: i64) -> bool {
+        !self.closed && (self.closes_at == 0 || now < self.closes_at)
+    }
+}
+
+// one vote per user per poll
+#[account]
+pub struct PollVote {
+    pub post: Pubkey, // 32 byte - post account of the poll
+    pub uid: u32,     // 4 byte - user id of the voter
+    pub option: u8,   // 1 byte - option voted for
+    pub weight: u64,  // 8 byte - weight of the vote
+    pub ts: i64,      // 8 byte - timestamp
+    pub bump: u8,     // 1 byte - bump
+}
+
+// a saved post, one account per user and post
+#[account]
+pub struct Bookmark {
+    pub uid: u32,     // 4 byte - user id
+    pub post: Pubkey, // 32 byte - bookmarked post account
+    pub ts: i64,      // 8 byte - timestamp
+    pub bump: u8,     // 1 byte - bump
+}
+
+// named, ordered list of posts, seeded by the user id and the name
+#[account]
+pub struct Collection {
+    pub uid: u32,           // 4 byte - user id of the owner
+    pub name: String,       // name (max 32 bytes)
+    pub visibility: u8,     // 1 byte - public or private
+    pub posts: Vec<Pubkey>, // post accounts, in order (max 100)
+    pub ts: i64,            // 8 byte - timestamp of the last change
+    pub bump: u8,           // 1 byte - bump
+}
+
+impl Collection {
+    pub fn space(name: &str, posts: usize) -> usize {
+        8 + mem::size_of::<Collection>() + name.len() + posts * mem::size_of::<Pubkey>()
+    }
+}
+
+// named list of users, seeded by the user id and the name
+// every member and subscriber has its own account, so lists can grow large
+#[account]
+pub struct List {
+    pub uid: u32,         // 4 byte - user id of the owner
+    pub name: String,     // name (max 32 bytes)
+    pub visibility: u8,   // 1 byte - public or private
+    pub members: u32,     // 4 byte - number of members
+    pub subscribers: u32, // 4 byte - number of subscribers
+    pub ts: i64,          // 8 byte - timestamp
+    pub bump: u8,         // 1 byte - bump
+}
+
+#[account]
+pub struct ListMember {
+    pub list: Pubkey, // 32 byte - list account
+    pub uid: u32,     // 4 byte - user id of the member
+    pub ts: i64,      // 8 byte - timestamp
+    pub bump: u8,     // 1 byte - bump
+}
+
+#[account]
+pub struct ListSubscription {
+    pub list: Pubkey, // 32 byte - list account
+    pub uid: u32,     // 4 byte - user id of the subscriber
+    pub ts: i64,      // 8 byte - timestamp
+    pub bump: u8,     // 1 byte - bump
+    pub list_ts: i64, // 8 byte - creation timestamp of the list, tells a re-created list apart
+}
+
+// names of collections and lists are used as seeds
+pub fn check_name(name: &str) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_NAME_LENGTH,
+        SplingError::InvalidName
+    );
+    Ok(())
+}
+
+pub fn check_visibility(visibility: u8) -> Result<()> {
+    require!(
+        visibility == VISIBILITY_PUBLIC || visibility == VISIBILITY_PRIVATE,
+        SplingError::InvalidVisibility
+    );
+    Ok(())
 }
 
 #[account]
@@ -25,13 +520,75 @@ pub struct Tip {
 
 #[account]
 pub struct Likes {
-    pub counter: u16,    // 2 byte - counts the number of likes
-    pub users: Vec<u32>, // user id's that like the post
-    pub bump: u8,        // 1 byte - bump
+    pub counter: u16,     // 2 byte - counts the number of likes
+    pub users: Vec<u32>,  // user id's that like the post
+    pub bump: u8,         // 1 byte - bump
+    pub epochs: Vec<u32>, // reward epoch each like earned points in, in the order of the users
+}
+
+// layout of the likes before they recorded the reward epoch, see migrate_account
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyLikes {
+    pub counter: u16,
+    pub users: Vec<u32>,
+    pub bump: u8,
+}
+
+impl LegacyLikes {
+    // the earlier likes earned in a finished epoch, or nothing at all
+    pub fn migrate(self) -> Likes {
+        Likes {
+            counter: self.counter,
+            epochs: vec![NO_REWARD_EPOCH; self.users.len()],
+            users: self.users,
+            bump: self.bump,
+        }
+    }
+}
+
+impl Likes {
+    pub fn add(&mut self, uid: u32, epoch: u32) {
+        // likes from before the epochs were recorded earned nothing that can be taken back
+        self.epochs.resize(self.users.len(), NO_REWARD_EPOCH);
+        self.users.push(uid);
+        self.epochs.push(epoch);
+        self.counter += 1;
+    }
+
+    // remove the like of a user, returns the reward epoch it earned points in
+    pub fn remove(&mut self, uid: u32) -> u32 {
+        match self.users.iter().position(|x| *x == uid) {
+            Some(index) => {
+                self.users.remove(index);
+                self.counter -= 1;
+                if index < self.epochs.len() {
+                    self.epochs.remove(index)
+                } else {
+                    NO_REWARD_EPOCH
+                }
+            }
+            None => NO_REWARD_EPOCH,
+        }
+    }
 }
 
 #[account]
 pub struct Reply {
+    pub ts: i64,       // 8 byte - timestamp
+    pub uid: u32,      // 4 byte - user id (max 4,294,967,295)
+    pub pid: u32,      // 4 byte - post id (max 4,294,967,295)
+    pub post: Pubkey,  // 32 byte - post account the reply relates to
+    pub user: Pubkey,  // 32 byte - public key of the author
+    pub payer: Pubkey, // 32 byte - account that paid the rent, refunded on close
+    pub st: u8,        // 1 byte - status (default = 1)
+    pub bump: u8,      // 1 byte - bump
+    pub post_uid: u32, // 4 byte - user id of the post author, who earned points for the reply
+    pub epoch: u32,    // 4 byte - reward epoch the post author earned points in
+}
+
+// layout of a reply before it referenced its post and author, see migrate_account
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyReply {
     pub ts: i64,  // 8 byte - timestamp
     pub uid: u32, // 4 byte - user id (max 4,294,967,295)
     pub pid: u32, // 4 byte - post id (max 4,294,967,295)
@@ -39,15 +596,169 @@ pub struct Reply {
     pub bump: u8, // 1 byte - bump
 }
 
+impl LegacyReply {
+    // the payer of a reply wasn't recorded, so the rent goes back to its author
+    pub fn migrate(self, post: Pubkey, post_uid: u32, user: Pubkey) -> Reply {
+        Reply {
+            ts: self.ts,
+            uid: self.uid,
+            pid: self.pid,
+            post,
+            user,
+            payer: user,
+            st: self.st,
+            bump: self.bump,
+            post_uid,
+            epoch: NO_REWARD_EPOCH,
+        }
+    }
+}
+
+// ring buffer of notifications, one account per user
+#[account]
+pub struct Inbox {
+    pub uid: u32,                                // 4 byte - user id of the owner
+    pub count: u32,                              // 4 byte - notifications received so far
+    pub read: u32,                               // 4 byte - read cursor, count at the last read
+    pub entries: [Notification; INBOX_CAPACITY], // entry n is stored at n % capacity
+    pub bump: u8,                                // 1 byte - bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Notification {
+    pub kind: u8,    // 1 byte - like, reply, follow or mention
+    pub uid: u32,    // 4 byte - user id that caused the notification
+    pub key: Pubkey, // 32 byte - post or reply account (default for follows)
+    pub ts: i64,     // 8 byte - timestamp
+}
+
+impl Inbox {
+    pub fn pubkey(uid: u32) -> Pubkey {
+        Pubkey::find_program_address(&[b"inbox", uid.to_le_bytes().as_ref()], &crate::ID).0
+    }
+
+    // overwrite the oldest entry once the inbox is full
+    pub fn push(&mut self, notification: Notification) {
+        self.entries[self.count as usize % INBOX_CAPACITY] = notification;
+        self.count += 1;
+    }
+
+    // unread notifications that are still in the inbox
+    pub fn unread(&self) -> u32 {
+        (self.count - self.read).min(INBOX_CAPACITY as u32)
+    }
+
+    // the inbox address of a user must be passed in the remaining accounts, so a
+    // notification can't be dropped by leaving it out
+    pub fn find<'a, 'info>(
+        accounts: &'a [AccountInfo<'info>],
+        uid: u32,
+    ) -> Result<&'a AccountInfo<'info>> {
+        find_account(accounts, &Inbox::pubkey(uid)).ok_or_else(|| SplingError::InboxMissing.into())
+    }
+
+    // append to the inbox of a user, passed in the remaining accounts
+    // users without an inbox, or notifying themselves, are skipped
+    pub fn notify(accounts: &[AccountInfo], uid: u32, notification: Notification) -> Result<()> {
+        if uid == notification.uid {
+            return Ok(());
+        }
+        let info = Inbox::find(accounts, uid)?;
+        if info.data_is_empty() {
+            return Ok(());
+        }
+        require!(info.is_writable, SplingError::InboxNotWritable);
+
+        let mut inbox: Account<Inbox> = Account::try_from(info)?;
+        inbox.push(notification);
+        inbox.exit(&crate::ID)
+    }
+}
+
+// X25519 public key a user publishes to receive encrypted direct messages
+#[account]
+pub struct MessagingKey {
+    pub uid: u32,      // 4 byte - user id
+    pub key: [u8; 32], // 32 byte - X25519 public key
+    pub ts: i64,       // 8 byte - timestamp of the last change
+    pub bump: u8,      // 1 byte - bump
+}
+
+// a blocked user can't open a conversation or send messages to the blocker
+#[account]
+pub struct Block {
+    pub uid: u32,     // 4 byte - user id of the blocker
+    pub blocked: u32, // 4 byte - user id of the blocked user
+    pub ts: i64,      // 8 byte - timestamp
+    pub bump: u8,     // 1 byte - bump
+}
+
+impl Block {
+    pub fn pubkey(uid: u32, blocked: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"block",
+                uid.to_le_bytes().as_ref(),
+                blocked.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+}
+
+// one conversation per pair of users, seeded by the lowest and then the highest user id
+#[account]
+pub struct Conversation {
+    pub users: [u32; 2], // 8 byte - user ids, lowest first
+    pub messages: u32,   // 4 byte - doubles as count of messages and message index
+    pub ts: i64,         // 8 byte - timestamp of the last message
+    pub bump: u8,        // 1 byte - bump
+}
+
+// a message points to ciphertext kept in the sender's shadow storage
+#[account]
+pub struct Message {
+    pub conversation: Pubkey, // 32 byte - conversation account
+    pub index: u32,           // 4 byte - index in the conversation
+    pub uid: u32,             // 4 byte - user id of the sender
+    pub shdw: Pubkey,         // 32 byte - content pointer of the ciphertext
+    pub nonce: [u8; 24],      // 24 byte - nonce used to encrypt the content
+    pub ts: i64,              // 8 byte - timestamp
+    pub bump: u8,             // 1 byte - bump
+}
+
 #[account]
 pub struct Spling {
     pub users: u32,  // doubles as count of users and user id's
     pub groups: u32, // doubles as count of groups and group id's
     pub posts: u32,  // doubles as count of posts and post id's
-    pub tags: u16,   // doubles as count of tags
+    pub tags: u32,   // doubles as count of tags
     pub bump: u8,
 }
 
+// layout of the spling account before tag ids were widened, see migrate_account
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacySpling {
+    pub users: u32,
+    pub groups: u32,
+    pub posts: u32,
+    pub tags: u16,
+    pub bump: u8,
+}
+
+impl LegacySpling {
+    pub fn migrate(self) -> Spling {
+        Spling {
+            users: self.users,
+            groups: self.groups,
+            posts: self.posts,
+            tags: self.tags as u32,
+            bump: self.bump,
+        }
+    }
+}
+
 // Bank serves as a container with rent
 // on a new post, the bank can be reallocated smaller
 // the rent that is released can pay for the transaction
@@ -62,6 +773,30 @@ pub struct B {}
 
 #[account]
 pub struct UserProfile {
+    pub ts: i64,                        // timestamp
+    pub user: Pubkey,                   // primary wallet of the user
+    pub wallets: [Pubkey; MAX_WALLETS], // other wallets that can sign (default = empty)
+    pub uid: u32,                       // user id (max 4,294,967,295)
+    pub st: u8,                         // status (default = 1)
+    pub shdw: Pubkey,                   // public key of user's shadow storage account
+    pub groups: Vec<u32>,               // group id's the user is member of
+    pub following: Vec<u32>,            // user id's the user is following
+    pub referrer: u32,                  // user id of the referrer (0 = none)
+    pub referrals: u32,                 // users this user referred
+    pub referral_rewards: u64,          // tokens earned from referrals
+    pub referral_paid: bool,            // the referrer of this user is rewarded
+    pub posts: u32,                     // posts submitted, deleted posts included
+    pub likes: u32,                     // likes received from other users
+    pub reputation: i64,                // reputation from likes, replies and moderation
+    pub bump: u8,
+    pub live_posts: u32,     // posts that are not deleted, expired or removed
+    pub referrer_likes: u32, // likes received from the referrer, which don't count as a milestone
+    pub reserved: [u8; 64],  // room for fields added later, so profiles don't need a migration
+}
+
+// layout of a profile before profiles were seeded by user id, see migrate_user_profile
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyUserProfile {
     pub ts: i64,             // timestamp
     pub user: Pubkey,        // user public key
     pub uid: u32,            // user id (max 4,294,967,295)
@@ -72,6 +807,686 @@ pub struct UserProfile {
     pub bump: u8,
 }
 
+impl LegacyUserProfile {
+    pub fn load(info: &AccountInfo) -> Result<LegacyUserProfile> {
+        load_legacy(info, UserProfile::discriminator())
+    }
+}
+
+impl UserProfile {
+    pub fn pubkey(uid: u32) -> Pubkey {
+        Pubkey::find_program_address(&[b"user_profile", uid.to_le_bytes().as_ref()], &crate::ID).0
+    }
+
+    // reputation including account age, which is added on read so it doesn't need a crank
+    pub fn reputation_score(&self, weights: &ReputationConfig, now: i64) -> i64 {
+        let days = (now - self.ts).max(0) / SECONDS_PER_DAY;
+        self.reputation
+            .saturating_add(days.saturating_mul(weights.age))
+    }
+
+    // change the profile of another user, deleted profiles are skipped
+    pub fn update(info: &AccountInfo, f: impl FnOnce(&mut UserProfile)) -> Result<()> {
+        if info.data_is_empty() {
+            return Ok(());
+        }
+        let mut profile: Account<UserProfile> = Account::try_from(info)?;
+        f(&mut profile);
+        profile.exit(&crate::ID)
+    }
+
+    pub fn add_reputation(info: &AccountInfo, delta: i64) -> Result<()> {
+        if delta == 0 {
+            return Ok(());
+        }
+        UserProfile::update(info, |profile| {
+            profile.reputation = profile.reputation.saturating_add(delta)
+        })
+    }
+
+    // the primary wallet and the other wallets can all sign for the profile
+    pub fn is_wallet(&self, key: &Pubkey) -> bool {
+        *key == self.user || (*key != Pubkey::default() && self.wallets.contains(key))
+    }
+
+    // the owner signs, or a session key that is allowed to do the action
+    // session keys are passed in the remaining accounts
+    pub fn authorize(
+        &self,
+        signer: &Pubkey,
+        accounts: &[AccountInfo],
+        action: u32,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        if self.is_wallet(signer) {
+            return Ok(());
+        }
+
+        let info = find_account(accounts, &SessionKey::pubkey(self.uid, signer))
+            .ok_or(SplingError::NotAuthorized)?;
+        let mut session: Account<SessionKey> = Account::try_from(info)?;
+        let clock: Clock = Clock::get()?;
+        require!(
+            self.is_wallet(&session.owner)
+                && clock.unix_timestamp < session.expiry
+                && session.allowed & action != 0,
+            SplingError::NotAuthorized
+        );
+
+        // token payments count towards the spend limit of the session
+        if let Some(am) = amount {
+            session.spent = session
+                .spent
+                .checked_add(am)
+                .filter(|spent| *spent <= session.spend_limit)
+                .ok_or(SplingError::SpendLimitExceeded)?;
+            require!(info.is_writable, SplingError::SpendLimitExceeded);
+            session.exit(&crate::ID)?;
+        }
+        Ok(())
+    }
+}
+
+// ephemeral key an app signs with for a profile, so not every action needs a wallet popup
+#[account]
+pub struct SessionKey {
+    pub uid: u32,         // 4 byte - user id
+    pub owner: Pubkey,    // 32 byte - wallet that created the session, invalid once removed
+    pub session: Pubkey,  // 32 byte - public key of the ephemeral keypair
+    pub expiry: i64,      // 8 byte - timestamp the session ends
+    pub allowed: u32,     // 4 byte - mask of allowed actions
+    pub spend_limit: u64, // 8 byte - max tokens the session can spend (0 = none)
+    pub spent: u64,       // 8 byte - tokens spent so far
+    pub bump: u8,         // 1 byte - bump
+}
+
+impl SessionKey {
+    pub fn pubkey(uid: u32, session: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"session_key", uid.to_le_bytes().as_ref(), session.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+}
+
+// maps a wallet to the user id of its profile, so a profile can be found by wallet
+#[account]
+pub struct Wallet {
+    pub uid: u32,       // 4 byte - user id
+    pub wallet: Pubkey, // 32 byte - wallet public key
+    pub bump: u8,       // 1 byte - bump
+}
+
+// guardians that can move a profile to a new wallet, when its keys are lost
+#[account]
+pub struct Guardians {
+    pub uid: u32,                             // 4 byte - user id of the profile
+    pub guardians: [Guardian; MAX_GUARDIANS], // guardian users or wallets (default = empty)
+    pub threshold: u8,                        // 1 byte - approvals needed to recover
+    pub delay: i64,                           // 8 byte - seconds between approval and execution
+    pub recoveries: u32,                      // 4 byte - recoveries initiated so far
+    pub pending: bool,                        // 1 byte - a recovery is in progress
+    pub bump: u8,                             // 1 byte - bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+pub struct Guardian {
+    pub uid: u32,       // 4 byte - user id of a guardian profile (0 = wallet guardian)
+    pub wallet: Pubkey, // 32 byte - guardian wallet (default = user guardian)
+}
+
+impl Guardians {
+    // guardians are other users or wallets, each named once, the profile can't guard itself
+    // profiles of user guardians are passed in the remaining accounts
+    pub fn check(
+        profile: &UserProfile,
+        guardians: &[Guardian],
+        threshold: u8,
+        delay: i64,
+        accounts: &[AccountInfo],
+    ) -> Result<()> {
+        require!(
+            !guardians.is_empty()
+                && guardians.len() <= MAX_GUARDIANS
+                && threshold > 0
+                && threshold as usize <= guardians.len(),
+            SplingError::InvalidGuardians
+        );
+        for (i, guardian) in guardians.iter().enumerate() {
+            let valid = match (guardian.uid, guardian.wallet == Pubkey::default()) {
+                (0, false) => !profile.is_wallet(&guardian.wallet),
+                (uid, true) => uid != 0 && uid != profile.uid,
+                _ => false,
+            };
+            require!(
+                valid && !guardians[..i].contains(guardian),
+                SplingError::InvalidGuardians
+            );
+            if guardian.uid != 0 {
+                let info = find_account(accounts, &UserProfile::pubkey(guardian.uid))
+                    .ok_or(SplingError::InvalidGuardians)?;
+                let guardian_profile: Account<UserProfile> = Account::try_from(info)?;
+                require!(
+                    !Guardian::shares_wallet(&guardian_profile, guardians),
+                    SplingError::InvalidGuardians
+                );
+            }
+        }
+        require!(
+            (MIN_RECOVERY_DELAY..=MAX_RECOVERY_DELAY).contains(&delay),
+            SplingError::InvalidRecoveryDelay
+        );
+        Ok(())
+    }
+}
+
+impl Guardian {
+    // a user guardian holding the wallet of a wallet guardian would approve twice
+    pub fn shares_wallet(profile: &UserProfile, guardians: &[Guardian]) -> bool {
+        guardians
+            .iter()
+            .any(|guardian| guardian.uid == 0 && profile.is_wallet(&guardian.wallet))
+    }
+
+    // a wallet guardian signs itself, a user guardian signs with a wallet of its profile
+    // the profile of a user guardian is passed in the remaining accounts
+    pub fn check_signer(
+        &self,
+        signer: &Pubkey,
+        guardians: &[Guardian],
+        accounts: &[AccountInfo],
+    ) -> Result<()> {
+        if self.wallet != Pubkey::default() {
+            require!(*signer == self.wallet, SplingError::NotAGuardian);
+            return Ok(());
+        }
+        require!(self.uid != 0, SplingError::NotAGuardian);
+
+        let info = find_account(accounts, &UserProfile::pubkey(self.uid))
+            .ok_or(SplingError::NotAGuardian)?;
+        let profile: Account<UserProfile> = Account::try_from(info)?;
+        require!(profile.is_wallet(signer), SplingError::NotAGuardian);
+
+        // the profile may have added the wallet after the guardians were set
+        require!(
+            !Guardian::shares_wallet(&profile, guardians),
+            SplingError::InvalidGuardians
+        );
+        Ok(())
+    }
+}
+
+// request of the guardians to move a profile to a new primary wallet
+#[account]
+pub struct Recovery {
+    pub uid: u32,          // 4 byte - user id of the profile
+    pub index: u32,        // 4 byte - recovery number of the profile
+    pub wallet: Pubkey,    // 32 byte - new primary wallet
+    pub initiator: Pubkey, // 32 byte - guardian wallet that initiated the recovery
+    pub approvals: u8,     // 1 byte - guardians that approved so far
+    pub status: u8,        // 1 byte - pending, cancelled or executed
+    pub ts: i64,           // 8 byte - timestamp the recovery was initiated
+    pub approved_at: i64,  // 8 byte - timestamp the threshold was reached (0 = not yet)
+    pub bump: u8,          // 1 byte - bump
+}
+
+impl Recovery {
+    // a recovery that doesn't reach the threshold in time, or isn't executed in time after
+    // the delay, no longer blocks the guardians
+    pub fn is_expired(&self, delay: i64, now: i64) -> bool {
+        if self.approved_at == 0 {
+            now >= self.ts + RECOVERY_APPROVAL_WINDOW
+        } else {
+            now >= self.approved_at + delay + RECOVERY_APPROVAL_WINDOW
+        }
+    }
+}
+
+// approval of one guardian, one per guardian and recovery
+#[account]
+pub struct RecoveryApproval {
+    pub recovery: Pubkey, // 32 byte - recovery account
+    pub guardian: u8,     // 1 byte - index of the guardian
+    pub signer: Pubkey,   // 32 byte - wallet that signed the approval
+    pub ts: i64,          // 8 byte - timestamp
+    pub bump: u8,         // 1 byte - bump
+}
+
+// party the admin allows to attest users, e.g. for verification or team membership
+#[account]
+pub struct Issuer {
+    pub issuer: Pubkey, // 32 byte - wallet that signs attestations
+    pub kinds: u32,     // 4 byte - mask of attestation kinds it may issue (0 = none)
+    pub ts: i64,        // 8 byte - timestamp of the last change
+    pub bump: u8,       // 1 byte - bump
+}
+
+impl Issuer {
+    pub fn pubkey(issuer: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"issuer", issuer.as_ref()], &crate::ID).0
+    }
+
+    pub fn allows(&self, kind: u8) -> bool {
+        kind > 0 && kind <= MAX_ATTESTATION_KIND && self.kinds & (1 << kind) != 0
+    }
+}
+
+// one attestation per user and kind, so it's found from the user id alone
+#[account]
+pub struct Attestation {
+    pub uid: u32,            // 4 byte - user id
+    pub kind: u8,            // 1 byte - verified, official org, team member, ...
+    pub issuer: Pubkey,      // 32 byte - wallet of the issuer
+    pub expiry: i64,         // 8 byte - timestamp the attestation ends (0 = never)
+    pub revoked: bool,       // 1 byte - revoked by the issuer or the admin
+    pub ts: i64,             // 8 byte - timestamp
+    pub bump: u8,            // 1 byte - bump
+    pub admin_revoked: bool, // 1 byte - revoked by the admin, no issuer can issue it again
+}
+
+impl Attestation {
+    pub fn pubkey(uid: u32, kind: u8) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"attestation",
+                uid.to_le_bytes().as_ref(),
+                kind.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    // the issuer account has to still exist and allow the kind, removing an issuer
+    // or taking away a kind ends the attestations it issued
+    pub fn is_valid(&self, issuer: &AccountInfo, now: i64) -> Result<bool> {
+        if self.revoked || (self.expiry != 0 && now >= self.expiry) {
+            return Ok(false);
+        }
+        if issuer.data_is_empty() || *issuer.key != Issuer::pubkey(self.issuer) {
+            return Ok(false);
+        }
+        let issuer: Account<Issuer> = Account::try_from(issuer)?;
+        Ok(issuer.allows(self.kind))
+    }
+
+    // the attestation and its issuer are passed in the remaining accounts
+    // users without an attestation account are not attested
+    pub fn is_attested(accounts: &[AccountInfo], uid: u32, kind: u8) -> Result<bool> {
+        let info = match find_account(accounts, &Attestation::pubkey(uid, kind)) {
+            Some(info) if !info.data_is_empty() => info,
+            _ => return Ok(false),
+        };
+        let attestation: Account<Attestation> = Account::try_from(info)?;
+        let issuer = match find_account(accounts, &Issuer::pubkey(attestation.issuer)) {
+            Some(issuer) => issuer,
+            None => return Ok(false),
+        };
+        let clock: Clock = Clock::get()?;
+        attestation.is_valid(issuer, clock.unix_timestamp)
+    }
+}
+
+// milestone of a user, awarded once per kind
+#[account]
+pub struct Badge {
+    pub uid: u32,     // 4 byte - user id
+    pub kind: u8,     // 1 byte - first post, hundred likes, early user, group founder
+    pub minted: bool, // 1 byte - minted as a frozen token
+    pub ts: i64,      // 8 byte - timestamp
+    pub bump: u8,     // 1 byte - bump
+}
+
+impl Badge {
+    pub fn pubkey(uid: u32, kind: u8) -> Pubkey {
+        Pubkey::find_program_address(&[b"badge", &uid.to_le_bytes(), &[kind]], &crate::ID).0
+    }
+
+    // check the milestone of a badge on the profile counters, the join order of a group isn't
+    // kept, so only join_group awards founding members
+    pub fn is_earned(kind: u8, profile: &UserProfile) -> Result<bool> {
+        Ok(match kind {
+            BADGE_FIRST_POST => profile.posts >= 1,
+            BADGE_HUNDRED_LIKES => profile.likes >= BADGE_LIKES,
+            BADGE_EARLY_USER => profile.uid <= BADGE_EARLY_USERS,
+            BADGE_GROUP_FOUNDER => false,
+            _ => return err!(SplingError::InvalidBadgeKind),
+        })
+    }
+
+    // the members that joined a group first are its founding members
+    pub fn is_founding_member(joined: u16) -> bool {
+        joined <= BADGE_FOUNDING_MEMBERS
+    }
+
+    // award a badge as its milestone is reached, the badge account is passed in the remaining
+    // accounts, without it the milestone is emitted and the badge is left to award_badge
+    pub fn award<'info>(
+        accounts: &[AccountInfo<'info>],
+        uid: u32,
+        kind: u8,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let uid_bytes = uid.to_le_bytes();
+        let (key, bump) =
+            Pubkey::find_program_address(&[b"badge", &uid_bytes, &[kind]], &crate::ID);
+        let ts = Clock::get()?.unix_timestamp;
+
+        match find_account(accounts, &key) {
+            // awarded before, likes can drop below the milestone and reach it again
+            Some(info) if !info.data_is_empty() => Ok(()),
+            Some(info) => {
+                create_account(
+                    payer,
+                    info,
+                    system_program,
+                    8 + mem::size_of::<Badge>(),
+                    &[b"badge", &uid_bytes, &[kind], &[bump]],
+                )?;
+                let mut data = info.try_borrow_mut_data()?;
+                Badge {
+                    uid,
+                    kind,
+                    minted: false,
+                    ts,
+                    bump,
+                }
+                .try_serialize(&mut &mut data[..])?;
+
+                emit!(BadgeAwarded { uid, kind, ts });
+                Ok(())
+            }
+            None => {
+                emit!(BadgeEarned { uid, kind, ts });
+                Ok(())
+            }
+        }
+    }
+}
+
+// pool of creator rewards, the reward vault holds its tokens
+#[account]
+pub struct RewardPool {
+    pub mint: Pubkey,      // 32 byte - mint of the reward token
+    pub emission: u64,     // 8 byte - tokens paid out per epoch
+    pub epoch_length: i64, // 8 byte - minimum seconds per epoch
+    pub epoch: u32,        // 4 byte - current epoch
+    pub epoch_start: i64,  // 8 byte - timestamp the current epoch started
+    pub points: u64,       // 8 byte - points earned in the current epoch
+    pub reserved: u64,     // 8 byte - tokens of past epochs that are not claimed yet
+    pub bump: u8,          // 1 byte - bump
+}
+
+impl RewardPool {
+    pub fn pubkey() -> Pubkey {
+        Pubkey::find_program_address(&[b"reward_pool"], &crate::ID).0
+    }
+
+    // points only accrue once rewards are set up, then the creator points of the current epoch
+    // are passed in the remaining accounts, and created by the first like or reply of the epoch,
+    // returns the epoch the points were earned in
+    pub fn accrue_points<'info>(
+        pool: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        uid: u32,
+        points: u64,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<u32> {
+        if pool.data_is_empty() {
+            return Ok(NO_REWARD_EPOCH);
+        }
+        let mut reward_pool: Account<RewardPool> = Account::try_from(pool)?;
+
+        let uid_bytes = uid.to_le_bytes();
+        let epoch_bytes = reward_pool.epoch.to_le_bytes();
+        let (key, bump) = Pubkey::find_program_address(
+            &[b"creator_points", &uid_bytes, &epoch_bytes],
+            &crate::ID,
+        );
+        let info = find_account(accounts, &key)
+            .filter(|info| info.is_writable)
+            .ok_or(SplingError::CreatorPointsMissing)?;
+
+        if info.data_is_empty() {
+            create_account(
+                payer,
+                info,
+                system_program,
+                8 + mem::size_of::<CreatorPoints>(),
+                &[b"creator_points", &uid_bytes, &epoch_bytes, &[bump]],
+            )?;
+            let mut data = info.try_borrow_mut_data()?;
+            CreatorPoints {
+                uid,
+                epoch: reward_pool.epoch,
+                points: 0,
+                payer: payer.key(),
+                bump,
+            }
+            .try_serialize(&mut &mut data[..])?;
+        }
+
+        let mut creator: Account<CreatorPoints> = Account::try_from(info)?;
+        reward_pool.accrue(&mut creator, points as i64);
+        creator.exit(&crate::ID)?;
+        reward_pool.exit(&crate::ID)?;
+        Ok(reward_pool.epoch)
+    }
+
+    // points are taken back from the epoch they were earned in, as long as it runs, the totals
+    // of a finished epoch are final
+    pub fn take_back_points<'info>(
+        pool: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        uid: u32,
+        points: u64,
+        epoch: u32,
+    ) -> Result<()> {
+        if epoch == NO_REWARD_EPOCH || pool.data_is_empty() {
+            return Ok(());
+        }
+        let mut reward_pool: Account<RewardPool> = Account::try_from(pool)?;
+        if reward_pool.epoch != epoch {
+            return Ok(());
+        }
+
+        let (key, _) = Pubkey::find_program_address(
+            &[b"creator_points", &uid.to_le_bytes(), &epoch.to_le_bytes()],
+            &crate::ID,
+        );
+        let info = find_account(accounts, &key)
+            .filter(|info| info.is_writable)
+            .ok_or(SplingError::CreatorPointsMissing)?;
+
+        let mut creator: Account<CreatorPoints> = Account::try_from(info)?;
+        reward_pool.accrue(&mut creator, -(points as i64));
+        creator.exit(&crate::ID)?;
+        reward_pool.exit(&crate::ID)
+    }
+
+    // add points of a creator to the current epoch, unlikes take them back
+    pub fn accrue(&mut self, creator: &mut CreatorPoints, points: i64) {
+        if points >= 0 {
+            creator.points = creator.points.saturating_add(points as u64);
+            self.points = self.points.saturating_add(points as u64);
+        } else {
+            let taken = creator.points.min(points.unsigned_abs());
+            creator.points -= taken;
+            self.points = self.points.saturating_sub(taken);
+        }
+    }
+}
+
+// totals of a finished epoch, creators claim their share from it
+#[account]
+pub struct RewardEpoch {
+    pub epoch: u32,    // 4 byte - epoch number
+    pub points: u64,   // 8 byte - points earned by all creators
+    pub emission: u64, // 8 byte - tokens paid out to all creators
+    pub claimed: u64,  // 8 byte - tokens claimed so far
+    pub bump: u8,      // 1 byte - bump
+}
+
+// points of a creator in one epoch
+#[account]
+pub struct CreatorPoints {
+    pub uid: u32,      // 4 byte - user id of the creator
+    pub epoch: u32,    // 4 byte - epoch number
+    pub points: u64,   // 8 byte - points from likes and replies on the posts of the creator
+    pub payer: Pubkey, // 32 byte - account that paid the rent, refunded on claim
+    pub bump: u8,      // 1 byte - bump
+}
+
+// tokens a user staked in the stake vault
+#[account]
+pub struct StakePosition {
+    pub uid: u32,         // 4 byte - user id
+    pub mint: Pubkey,     // 32 byte - mint of the staked token
+    pub amount: u64,      // 8 byte - staked tokens, these count for the perks
+    pub unstaking: u64,   // 8 byte - tokens in cooldown, these don't count
+    pub unlock_at: i64,   // 8 byte - timestamp the unstaking tokens can be withdrawn
+    pub bump: u8,         // 1 byte - bump
+    pub voted_until: i64, // 8 byte - close timestamp of the last poll the stake voted on
+}
+
+impl StakePosition {
+    pub fn pubkey(uid: u32) -> Pubkey {
+        Pubkey::find_program_address(&[b"stake_position", uid.to_le_bytes().as_ref()], &crate::ID).0
+    }
+
+    // users pass their stake position in the remaining accounts to get their perks
+    pub fn load(accounts: &[AccountInfo], uid: u32) -> Result<Option<StakePosition>> {
+        match find_account(accounts, &StakePosition::pubkey(uid)) {
+            Some(info) if !info.data_is_empty() => {
+                let position: Account<StakePosition> = Account::try_from(info)?;
+                Ok(Some(position.into_inner()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub fn staked(accounts: &[AccountInfo], uid: u32) -> Result<u64> {
+        Ok(StakePosition::load(accounts, uid)?.map_or(0, |position| position.amount))
+    }
+
+    // the stake only unlocks once the last poll it voted on closed
+    pub fn lock(&mut self, until: i64) {
+        self.voted_until = self.voted_until.max(until);
+    }
+
+    // the stake weighs a vote, and can't be withdrawn to vote again from another profile
+    // until the poll closed
+    pub fn vote<'info>(accounts: &[AccountInfo<'info>], uid: u32, poll: &Poll) -> Result<u64> {
+        let info = find_account(accounts, &StakePosition::pubkey(uid))
+            .filter(|info| info.is_writable && !info.data_is_empty())
+            .ok_or(SplingError::InvalidVoteStake)?;
+        let mut position: Account<StakePosition> = Account::try_from(info)?;
+        require!(
+            position.mint == poll.mint && position.amount > 0,
+            SplingError::InvalidVoteStake
+        );
+
+        position.lock(poll.closes_at);
+        position.exit(&crate::ID)?;
+        Ok(position.amount)
+    }
+}
+
+// terms a creator offers to paying subscribers
+#[account]
+pub struct SubscriptionTier {
+    pub uid: u32,              // 4 byte - user id of the creator
+    pub mint: Pubkey,          // 32 byte - mint of the token subscribers pay in
+    pub token_account: Pubkey, // 32 byte - creator token account receiving the payments
+    pub price: u64,            // 8 byte - tokens charged per period
+    pub period: i64,           // 8 byte - seconds per period, whole days
+    pub subscribers: u32,      // 4 byte - active subscriptions
+    pub bump: u8,              // 1 byte - bump
+}
+
+// a subscription of one user to a creator, the terms are fixed at subscribe time
+#[account]
+pub struct Subscription {
+    pub creator: u32,          // 4 byte - user id of the creator
+    pub subscriber: u32,       // 4 byte - user id of the subscriber
+    pub mint: Pubkey,          // 32 byte - mint of the token paid in
+    pub token_account: Pubkey, // 32 byte - subscriber token account, charged as delegate
+    pub recipient: Pubkey,     // 32 byte - creator token account receiving the payments
+    pub price: u64,            // 8 byte - tokens charged per period
+    pub period: i64,           // 8 byte - seconds per period
+    pub allowance: u64, // 8 byte - tokens of the delegate approval left for this subscription
+    pub paid_until: i64, // 8 byte - timestamp the subscription is paid up to
+    pub payer: Pubkey,  // 32 byte - paid the rent of the thread, refunded on delete
+    pub status: u8,     // 1 byte - active, cancelled or lapsed
+    pub bump: u8,       // 1 byte - bump
+}
+
+impl Subscription {
+    pub fn pubkey(creator: u32, subscriber: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"subscription",
+                creator.to_le_bytes().as_ref(),
+                subscriber.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    // a cancelled subscription stays active until the end of the paid period
+    pub fn is_active(&self, now: i64) -> bool {
+        now < self.paid_until
+    }
+
+    // subscriber-only content is checked with the subscription passed in the remaining accounts
+    pub fn is_subscribed(
+        accounts: &[AccountInfo],
+        creator: u32,
+        subscriber: u32,
+        now: i64,
+    ) -> Result<bool> {
+        if creator == subscriber {
+            return Ok(true);
+        }
+        match find_account(accounts, &Subscription::pubkey(creator, subscriber)) {
+            Some(info) if !info.data_is_empty() => {
+                let subscription: Account<Subscription> = Account::try_from(info)?;
+                Ok(subscription.is_active(now))
+            }
+            _ => Ok(false),
+        }
+    }
+
+    // the next period continues from the previous one, unless charges were missed for a whole period
+    pub fn extend(&mut self, now: i64) {
+        self.paid_until = if now < self.paid_until + self.period {
+            self.paid_until + self.period
+        } else {
+            now + self.period
+        };
+    }
+
+    // the delegate can only charge what is approved and in the subscriber token account
+    pub fn can_charge(&self, token_account: &AccountInfo, delegate: &Pubkey) -> bool {
+        if token_account.owner != &token::ID || self.allowance < self.price {
+            return false;
+        }
+        match TokenAccount::try_deserialize(&mut &token_account.data.borrow()[..]) {
+            Ok(account) => {
+                account.mint == self.mint
+                    && account.delegate == COption::Some(*delegate)
+                    && account.delegated_amount >= self.price
+                    && account.amount >= self.price
+            }
+            Err(_) => false,
+        }
+    }
+}
+
 #[account]
 pub struct GroupProfile {
     pub ts: i64,       // timestamp
@@ -80,4 +1495,677 @@ pub struct GroupProfile {
     pub st: u8,        // status (default = 1)
     pub shdw: Pubkey,  // public key of group's shadow storage account
     pub bump: u8,
+    pub joined: u16, // members joined so far, the join order decides the founding members
+}
+
+// per user settings, seeded by the user id so they can be checked from a post
+#[account]
+pub struct ProfileSettings {
+    pub uid: u32,        // 4 byte - user id
+    pub protected: bool, // 1 byte - only approved followers can like and reply
+    pub bump: u8,        // 1 byte - bump
+}
+
+impl ProfileSettings {
+    pub fn pubkey(uid: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"profile_settings", uid.to_le_bytes().as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    // users without a settings account are not protected
+    pub fn is_protected(info: &AccountInfo) -> Result<bool> {
+        if info.data_is_empty() {
+            return Ok(false);
+        }
+        let settings: Account<ProfileSettings> = Account::try_from(info)?;
+        Ok(settings.protected)
+    }
+}
+
+// request to follow a protected account, kept after approval as proof of it
+#[account]
+pub struct FollowRequest {
+    pub uid: u32,      // 4 byte - user id of the protected account
+    pub follower: u32, // 4 byte - user id of the requesting user
+    pub user: Pubkey,  // 32 byte - wallet that paid the rent, gets it back on close
+    pub status: u8,    // 1 byte - pending or approved
+    pub ts: i64,       // 8 byte - timestamp of the last change
+    pub bump: u8,      // 1 byte - bump
+}
+
+impl FollowRequest {
+    pub fn pubkey(uid: u32, follower: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"follow_request",
+                uid.to_le_bytes().as_ref(),
+                follower.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    // a user can interact with posts of a protected account once approved
+    pub fn check_access(
+        uid: u32,
+        follower: u32,
+        settings: &AccountInfo,
+        follow_request: &AccountInfo,
+    ) -> Result<()> {
+        if uid == follower || !ProfileSettings::is_protected(settings)? {
+            return Ok(());
+        }
+        require!(
+            !follow_request.data_is_empty(),
+            SplingError::NotAnApprovedFollower
+        );
+        let request: Account<FollowRequest> = Account::try_from(follow_request)?;
+        require!(
+            request.status == FOLLOW_REQUEST_APPROVED,
+            SplingError::NotAnApprovedFollower
+        );
+        Ok(())
+    }
+}
+
+// protocol wide settings, managed by the admin that set up the config
+#[account]
+pub struct Config {
+    pub admin: Pubkey,                // 32 byte - protocol admin
+    pub post_limit: ActionLimit,      // rate limit for posts
+    pub reply_limit: ActionLimit,     // rate limit for replies
+    pub like_limit: ActionLimit,      // rate limit for likes
+    pub boost: u16,                   // multiplier on max actions for boosted users
+    pub reputation: ReputationConfig, // reputation weights and thresholds
+    pub referral: ReferralConfig,     // referral reward and milestones
+    pub staking: StakingConfig,       // stake needed for the staking perks
+    pub bump: u8,                     // 1 byte - bump
+    pub reserved: [u8; 128],          // room for settings added later, without a migration
+}
+
+impl Config {
+    // users with enough reputation or stake get the boosted limit
+    pub fn limit_for(
+        &self,
+        limit: &ActionLimit,
+        profile: &UserProfile,
+        staked: u64,
+        now: i64,
+    ) -> ActionLimit {
+        let score = profile.reputation_score(&self.reputation, now);
+        if (self.reputation.boost > 0 && score >= self.reputation.boost)
+            || (self.staking.boost > 0 && staked >= self.staking.boost)
+        {
+            limit.boosted(self.boost)
+        } else {
+            *limit
+        }
+    }
+
+    // users below the subsidy threshold pay for their own replies, unless they stake enough
+    pub fn is_subsidized(&self, profile: &UserProfile, staked: u64, now: i64) -> bool {
+        profile.reputation_score(&self.reputation, now) >= self.reputation.subsidy
+            || (self.staking.subsidy > 0 && staked >= self.staking.subsidy)
+    }
+
+    pub fn max_tags(&self, staked: u64) -> usize {
+        if self.staking.tags > 0 && staked >= self.staking.tags {
+            MAX_STAKED_POST_TAGS
+        } else {
+            MAX_POST_TAGS
+        }
+    }
+}
+
+// stake a user needs for each of the perks (0 = off)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct StakingConfig {
+    pub cooldown: i64, // 8 byte - seconds between unstaking and withdrawing
+    pub boost: u64,    // 8 byte - stake for boosted rate limits
+    pub subsidy: u64,  // 8 byte - stake for subsidized replies
+    pub tags: u64,     // 8 byte - stake for more tags per post
+}
+
+// a referrer is rewarded once the referred user reaches all milestones
+// a second profile of the referrer is cheap, so the age and stake make referring yourself costly
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ReferralConfig {
+    pub reward: u64, // 8 byte - tokens paid from the referral vault (0 = off)
+    pub posts: u32,  // 4 byte - live posts of the referred user
+    pub likes: u32,  // 4 byte - likes the referred user received from others than the referrer
+    pub age: i64,    // 8 byte - seconds since the referred profile was created
+    pub stake: u64,  // 8 byte - tokens the referred user has staked
+}
+
+impl ReferralConfig {
+    pub fn is_reached(&self, referee: &UserProfile, staked: u64, now: i64) -> bool {
+        self.reward > 0
+            && referee.live_posts >= self.posts
+            && referee.likes.saturating_sub(referee.referrer_likes) >= self.likes
+            && now.saturating_sub(referee.ts) >= self.age
+            && staked >= self.stake
+    }
+}
+
+// weights are added to the reputation of the author, the moderation weight is subtracted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ReputationConfig {
+    pub like: i64,       // 8 byte - per like received
+    pub reply: i64,      // 8 byte - per reply received
+    pub age: i64,        // 8 byte - per day since the profile was created
+    pub moderation: i64, // 8 byte - per post removed by moderation
+    pub boost: i64,      // 8 byte - reputation for boosted rate limits (0 = off)
+    pub subsidy: i64,    // 8 byte - reputation for subsidized replies
+    pub group: i64,      // 8 byte - reputation to join groups (0 = off)
+}
+
+impl ReputationConfig {
+    pub fn can_join_group(&self, profile: &UserProfile, now: i64) -> bool {
+        self.group <= 0 || profile.reputation_score(self, now) >= self.group
+    }
+}
+
+// at most max actions per window (seconds), with at least interval seconds in between
+// a window of 0 disables the limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ActionLimit {
+    pub window: i64,   // 8 byte - length of the window in seconds
+    pub interval: i64, // 8 byte - minimum seconds between two actions
+    pub max: u16,      // 2 byte - max number of actions per window
+}
+
+impl ActionLimit {
+    // boosted users (e.g. reputation or stake) get a multiple of the max actions per window
+    pub fn boosted(&self, multiplier: u16) -> ActionLimit {
+        ActionLimit {
+            max: self.max.saturating_mul(multiplier.max(1)),
+            ..*self
+        }
+    }
+}
+
+// per user rate state, kept in a side PDA so the profile layout stays the same
+#[account]
+pub struct RateLimit {
+    pub uid: u32,               // 4 byte - user id
+    pub posts: ActionCounter,   // rolling counter for posts
+    pub replies: ActionCounter, // rolling counter for replies
+    pub likes: ActionCounter,   // rolling counter for likes
+    pub bump: u8,               // 1 byte - bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ActionCounter {
+    pub last: i64,         // 8 byte - timestamp of the last action
+    pub window_start: i64, // 8 byte - timestamp the current window started
+    pub count: u16,        // 2 byte - actions in the current window
+}
+
+impl ActionCounter {
+    // register an action at timestamp now, fails when the limit is exceeded
+    pub fn record(&mut self, limit: &ActionLimit, now: i64) -> Result<()> {
+        if limit.window > 0 {
+            // start a new window once the current one has passed
+            if now - self.window_start >= limit.window {
+                self.window_start = now;
+                self.count = 0;
+            }
+
+            require!(
+                now - self.last >= limit.interval,
+                SplingError::ActionTooSoon
+            );
+            require!(self.count < limit.max, SplingError::RateLimited);
+        }
+
+        self.count = self.count.saturating_add(1);
+        self.last = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: ActionLimit = ActionLimit {
+        window: 60,
+        interval: 5,
+        max: 2,
+    };
+
+    fn post(expiry: i64) -> Post {
+        Post {
+            ts: 0,
+            uid: 1,
+            pid: 1,
+            gid: 1,
+            tags: [0; MAX_STAKED_POST_TAGS],
+            mentions: [0; MAX_POST_MENTIONS],
+            expiry,
+            payer: Pubkey::default(),
+            st: POST_STATUS_ACTIVE,
+            bump: 0,
+            subscribers_only: false,
+        }
+    }
+
+    #[test]
+    fn post_expires_at_its_expiry() {
+        assert!(!post(2000).is_expired(1999));
+        assert!(post(2000).is_expired(2000));
+        assert!(post(2000).is_expired(5000));
+    }
+
+    #[test]
+    fn post_without_expiry_never_expires() {
+        assert!(!post(0).is_expired(i64::MAX));
+    }
+
+    #[test]
+    fn subscriber_only_post_open_to_its_author() {
+        let mut post = post(0);
+        assert!(post.check_subscriber(&[], 2, 0).is_ok());
+
+        post.subscribers_only = true;
+        assert!(post.check_subscriber(&[], 1, 0).is_ok());
+        assert!(post.check_subscriber(&[], 2, 0).is_err());
+    }
+
+    fn tag() -> Tag {
+        Tag {
+            id: 1,
+            name: "hello".to_string(),
+            posts: 0,
+            day: 0,
+            day_posts: 0,
+            score: 0,
+            uid: 1,
+            ts: 0,
+            bump: 0,
+            next: 0,
+        }
+    }
+
+    #[test]
+    fn removed_activity_on_the_same_day() {
+        let mut tag = tag();
+        tag.record_activity(10);
+        tag.record_activity(10);
+        tag.remove_activity(10, 10);
+        assert_eq!(tag.day_posts, 1);
+        assert_eq!(tag.score, TRENDING_SCORE_PER_POST);
+    }
+
+    #[test]
+    fn removed_activity_has_decayed() {
+        let mut tag = tag();
+        tag.record_activity(10);
+        tag.record_activity(10);
+        tag.remove_activity(10, 11);
+        assert_eq!(tag.day, 11);
+        assert_eq!(tag.day_posts, 0);
+        assert_eq!(tag.score, TRENDING_SCORE_PER_POST / 2);
+    }
+
+    #[test]
+    fn removed_activity_never_underflows() {
+        let mut tag = tag();
+        tag.remove_activity(10, 10);
+        assert_eq!(tag.day_posts, 0);
+        assert_eq!(tag.score, 0);
+    }
+
+    fn recovery(ts: i64, approved_at: i64) -> Recovery {
+        Recovery {
+            uid: 1,
+            index: 0,
+            wallet: Pubkey::new_unique(),
+            initiator: Pubkey::new_unique(),
+            approvals: 0,
+            status: RECOVERY_PENDING,
+            ts,
+            approved_at,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn recovery_expires_without_approval() {
+        assert!(!recovery(1000, 0).is_expired(500, 1000 + RECOVERY_APPROVAL_WINDOW - 1));
+        assert!(recovery(1000, 0).is_expired(500, 1000 + RECOVERY_APPROVAL_WINDOW));
+    }
+
+    #[test]
+    fn approved_recovery_expires_unless_executed() {
+        // the approval window no longer applies once the threshold is reached
+        assert!(!recovery(1000, 2000).is_expired(500, 1000 + RECOVERY_APPROVAL_WINDOW));
+        assert!(!recovery(1000, 2000).is_expired(500, 2500 + RECOVERY_APPROVAL_WINDOW - 1));
+        assert!(recovery(1000, 2000).is_expired(500, 2500 + RECOVERY_APPROVAL_WINDOW));
+    }
+
+    fn profile() -> UserProfile {
+        UserProfile {
+            ts: 0,
+            user: Pubkey::new_unique(),
+            wallets: [Pubkey::default(); MAX_WALLETS],
+            uid: 2,
+            st: 1,
+            shdw: Pubkey::default(),
+            groups: vec![],
+            following: vec![],
+            referrer: 0,
+            referrals: 0,
+            referral_rewards: 0,
+            referral_paid: false,
+            posts: 0,
+            likes: 0,
+            reputation: 0,
+            bump: 0,
+            live_posts: 0,
+            referrer_likes: 0,
+            reserved: [0; 64],
+        }
+    }
+
+    #[test]
+    fn user_guardian_holding_a_guardian_wallet() {
+        let wallet = Pubkey::new_unique();
+        let mut profile = profile();
+        profile.wallets[0] = wallet;
+        let user = Guardian {
+            uid: 2,
+            wallet: Pubkey::default(),
+        };
+        let other = Guardian {
+            uid: 0,
+            wallet: Pubkey::new_unique(),
+        };
+        let shared = Guardian { uid: 0, wallet };
+
+        assert!(!Guardian::shares_wallet(&profile, &[user, other]));
+        assert!(Guardian::shares_wallet(&profile, &[user, other, shared]));
+    }
+
+    #[test]
+    fn action_too_soon() {
+        let mut counter = ActionCounter::default();
+        counter.record(&LIMIT, 1000).unwrap();
+        assert_eq!(
+            counter.record(&LIMIT, 1004).unwrap_err(),
+            SplingError::ActionTooSoon.into()
+        );
+        counter.record(&LIMIT, 1005).unwrap();
+    }
+
+    #[test]
+    fn rate_limited_until_the_window_passes() {
+        let mut counter = ActionCounter::default();
+        counter.record(&LIMIT, 1000).unwrap();
+        counter.record(&LIMIT, 1010).unwrap();
+        assert_eq!(
+            counter.record(&LIMIT, 1020).unwrap_err(),
+            SplingError::RateLimited.into()
+        );
+
+        // a failed action doesn't count, and a new window starts over
+        assert_eq!(counter.count, 2);
+        counter.record(&LIMIT, 1060).unwrap();
+        assert_eq!(counter.count, 1);
+    }
+
+    #[test]
+    fn boosted_limit() {
+        let mut counter = ActionCounter::default();
+        let boosted = LIMIT.boosted(2);
+        for now in [1000, 1010, 1020, 1030] {
+            counter.record(&boosted, now).unwrap();
+        }
+        assert!(counter.record(&boosted, 1040).is_err());
+    }
+
+    #[test]
+    fn no_window_means_no_limit() {
+        let mut counter = ActionCounter::default();
+        let limit = ActionLimit { window: 0, ..LIMIT };
+        for _ in 0..10 {
+            counter.record(&limit, 1000).unwrap();
+        }
+    }
+
+    // an account as the earlier layout left it, padded to the size it was created with
+    fn legacy_account<T: AnchorSerialize>(discriminator: [u8; 8], legacy: &T) -> Vec<u8> {
+        let mut data = vec![0; 8 + mem::size_of::<T>()];
+        let bytes = legacy.try_to_vec().unwrap();
+        data[..8].copy_from_slice(&discriminator);
+        data[8..8 + bytes.len()].copy_from_slice(&bytes);
+        data
+    }
+
+    fn load<T: AnchorDeserialize>(data: &mut [u8], discriminator: [u8; 8]) -> T {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, true, &mut lamports, data, &crate::ID, false, 0);
+        load_legacy(&info, discriminator).unwrap()
+    }
+
+    // write the migrated account in the size migrate_account grows it to, and read it back
+    fn migrated<T: AccountSerialize + AccountDeserialize>(value: &T) -> T {
+        let mut data = vec![0; 8 + mem::size_of::<T>()];
+        value.try_serialize(&mut &mut data[..]).unwrap();
+        T::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn legacy_reply_migrates_to_its_post() {
+        let legacy = LegacyReply {
+            ts: 100,
+            uid: 2,
+            pid: 7,
+            st: 1,
+            bump: 254,
+        };
+        let mut data = legacy_account(Reply::discriminator(), &legacy);
+
+        // the layout is told apart by the size of the account
+        assert_ne!(data.len(), 8 + mem::size_of::<Reply>());
+
+        let post = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let legacy: LegacyReply = load(&mut data, Reply::discriminator());
+        let reply = migrated(&legacy.migrate(post, 3, user));
+        assert_eq!((reply.ts, reply.uid, reply.pid), (100, 2, 7));
+        assert_eq!((reply.st, reply.bump), (1, 254));
+        assert_eq!((reply.post, reply.post_uid), (post, 3));
+        assert_eq!((reply.user, reply.payer), (user, user));
+        assert_eq!(reply.epoch, NO_REWARD_EPOCH);
+    }
+
+    #[test]
+    fn legacy_post_migrates_without_tags() {
+        let legacy = LegacyPost {
+            ts: 100,
+            uid: 2,
+            pid: 7,
+            gid: 1,
+            tid: 4,
+            st: 1,
+            bump: 254,
+        };
+        let mut data = legacy_account(Post::discriminator(), &legacy);
+        assert_ne!(data.len(), 8 + mem::size_of::<Post>());
+
+        let author = Pubkey::new_unique();
+        let legacy: LegacyPost = load(&mut data, Post::discriminator());
+        let post = migrated(&legacy.migrate(author));
+        assert_eq!((post.ts, post.uid, post.pid, post.gid), (100, 2, 7, 1));
+        assert_eq!(post.tags, [0; MAX_STAKED_POST_TAGS]);
+        assert_eq!((post.expiry, post.payer), (0, author));
+        assert!(post.is_live());
+    }
+
+    #[test]
+    fn legacy_likes_earned_nothing_to_take_back() {
+        let legacy = LegacyLikes {
+            counter: 2,
+            users: vec![2, 3],
+            bump: 254,
+        };
+        let mut data = legacy_account(Likes::discriminator(), &legacy);
+        assert_ne!(data.len(), 8 + mem::size_of::<Likes>());
+
+        let legacy: LegacyLikes = load(&mut data, Likes::discriminator());
+        let mut likes = migrated(&legacy.migrate());
+        assert_eq!((likes.counter, likes.bump), (2, 254));
+        assert_eq!(likes.users, vec![2, 3]);
+        assert_eq!(likes.remove(3), NO_REWARD_EPOCH);
+    }
+
+    #[test]
+    fn group_gate_counts_account_age() {
+        let weights = ReputationConfig {
+            age: 1,
+            group: 10,
+            ..ReputationConfig::default()
+        };
+        let mut profile = profile();
+        profile.reputation = 5;
+
+        assert!(!weights.can_join_group(&profile, 4 * SECONDS_PER_DAY));
+        assert!(weights.can_join_group(&profile, 5 * SECONDS_PER_DAY));
+        assert!(ReputationConfig::default().can_join_group(&profile, 0));
+    }
+
+    #[test]
+    fn badges_earned_from_the_profile_counters() {
+        let mut profile = profile();
+        assert!(!Badge::is_earned(BADGE_FIRST_POST, &profile).unwrap());
+        assert!(!Badge::is_earned(BADGE_HUNDRED_LIKES, &profile).unwrap());
+        assert!(Badge::is_earned(BADGE_EARLY_USER, &profile).unwrap());
+
+        profile.posts = 1;
+        profile.likes = BADGE_LIKES;
+        profile.uid = BADGE_EARLY_USERS + 1;
+        assert!(Badge::is_earned(BADGE_FIRST_POST, &profile).unwrap());
+        assert!(Badge::is_earned(BADGE_HUNDRED_LIKES, &profile).unwrap());
+        assert!(!Badge::is_earned(BADGE_EARLY_USER, &profile).unwrap());
+        assert!(Badge::is_earned(0, &profile).is_err());
+    }
+
+    #[test]
+    fn founding_members_are_the_first_to_join() {
+        // the creator of a group doesn't count as a founder by itself
+        assert!(!Badge::is_earned(BADGE_GROUP_FOUNDER, &profile()).unwrap());
+        assert!(Badge::is_founding_member(1));
+        assert!(Badge::is_founding_member(BADGE_FOUNDING_MEMBERS));
+        assert!(!Badge::is_founding_member(BADGE_FOUNDING_MEMBERS + 1));
+    }
+
+    const REFERRAL: ReferralConfig = ReferralConfig {
+        reward: 100,
+        posts: 2,
+        likes: 3,
+        age: 86_400,
+        stake: 50,
+    };
+
+    fn referee() -> UserProfile {
+        let mut referee = profile();
+        referee.referrer = 1;
+        referee.live_posts = 2;
+        referee.likes = 3;
+        referee
+    }
+
+    #[test]
+    fn referral_reached_with_all_milestones() {
+        assert!(REFERRAL.is_reached(&referee(), 50, 86_400));
+        assert!(!REFERRAL.is_reached(&referee(), 49, 86_400));
+        assert!(!REFERRAL.is_reached(&referee(), 50, 86_399));
+    }
+
+    #[test]
+    fn referral_skips_likes_of_the_referrer() {
+        let mut referee = referee();
+        referee.referrer_likes = 1;
+        assert!(!REFERRAL.is_reached(&referee, 50, 86_400));
+        referee.likes = 4;
+        assert!(REFERRAL.is_reached(&referee, 50, 86_400));
+    }
+
+    #[test]
+    fn referral_counts_live_posts_only() {
+        let mut referee = referee();
+        referee.posts = 5;
+        referee.live_posts = 1;
+        assert!(!REFERRAL.is_reached(&referee, 50, 86_400));
+    }
+
+    #[test]
+    fn referral_off_without_reward() {
+        let referral = ReferralConfig {
+            reward: 0,
+            ..REFERRAL
+        };
+        assert!(!referral.is_reached(&referee(), 50, 86_400));
+    }
+
+    #[test]
+    fn unlike_returns_the_epoch_of_the_like() {
+        let mut likes = Likes {
+            counter: 0,
+            users: vec![],
+            bump: 0,
+            epochs: vec![],
+        };
+        likes.add(2, 4);
+        likes.add(3, NO_REWARD_EPOCH);
+        likes.add(4, 5);
+
+        assert_eq!(likes.remove(2), 4);
+        assert_eq!(likes.remove(4), 5);
+        assert_eq!(likes.remove(4), NO_REWARD_EPOCH);
+        assert_eq!(likes.users, vec![3]);
+        assert_eq!(likes.counter, 1);
+    }
+
+    #[test]
+    fn likes_before_the_epochs_earned_nothing() {
+        let mut likes = Likes {
+            counter: 2,
+            users: vec![2, 3],
+            bump: 0,
+            epochs: vec![],
+        };
+        likes.add(4, 5);
+
+        assert_eq!(likes.remove(3), NO_REWARD_EPOCH);
+        assert_eq!(likes.remove(4), 5);
+        assert_eq!(likes.remove(2), NO_REWARD_EPOCH);
+        assert_eq!(likes.counter, 0);
+    }
+
+    #[test]
+    fn stake_locked_until_the_last_poll_closes() {
+        let mut position = StakePosition {
+            uid: 2,
+            mint: Pubkey::default(),
+            amount: 100,
+            unstaking: 0,
+            unlock_at: 0,
+            bump: 0,
+            voted_until: 0,
+        };
+
+        // voting on a poll that closes earlier doesn't shorten the lock
+        position.lock(2000);
+        position.lock(1000);
+        assert_eq!(position.voted_until, 2000);
+        position.lock(3000);
+        assert_eq!(position.voted_until, 3000);
+    }
 }
@@ -0,0 +1,3 @@
+pub mod utils;
+
+pub use utils::*;
@@ -0,0 +1,181 @@
+use crate::*;
+
+// optional accounts are passed as remaining accounts, look one up by its address
+pub fn find_account<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    key: &Pubkey,
+) -> Option<&'a AccountInfo<'info>> {
+    accounts.iter().find(|account| account.key == key)
+}
+
+// create a program owned account at a PDA that is only known at runtime
+pub fn create_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        return anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    // anyone can send lamports to the address up front, which makes create_account fail,
+    // so like anchor's init, top up the rent and allocate and assign the account instead
+    let top_up = rent.saturating_sub(account.lamports());
+    if top_up > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    anchor_lang::system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    anchor_lang::system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+// fields of an account in an earlier layout, the discriminator of an account doesn't change
+pub fn load_legacy<T: AnchorDeserialize>(info: &AccountInfo, discriminator: [u8; 8]) -> Result<T> {
+    let data = info.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == discriminator,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(T::deserialize(&mut &data[8..])?)
+}
+
+// write an account in its current layout, growing it first, the payer tops up the rent
+pub fn write_migrated<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    value: &T,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let top_up = rent.saturating_sub(account.lamports());
+    if top_up > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    account.realloc(space, false)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
+
+// cron schedule (sec min hour day month weekday year) that fires once, at the given unix timestamp (UTC)
+pub fn schedule_at(unix_timestamp: i64) -> String {
+    let days = unix_timestamp.div_euclid(86400);
+    let secs = unix_timestamp.rem_euclid(86400);
+
+    // convert days since epoch into a civil date
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{} {} {} {} {} * {}",
+        secs % 60,
+        (secs / 60) % 60,
+        secs / 3600,
+        day,
+        month,
+        year
+    )
+}
+
+// cron schedule that fires every day, at the time of day of the given unix timestamp (UTC)
+pub fn schedule_daily(unix_timestamp: i64) -> String {
+    let secs = unix_timestamp.rem_euclid(86400);
+    format!("{} {} {} * * * *", secs % 60, (secs / 60) % 60, secs / 3600)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_at_epoch() {
+        assert_eq!(schedule_at(0), "0 0 0 1 1 * 1970");
+        assert_eq!(schedule_at(3661), "1 1 1 1 1 * 1970");
+    }
+
+    #[test]
+    fn schedule_at_leap_years() {
+        assert_eq!(schedule_at(951782400), "0 0 0 29 2 * 2000");
+        assert_eq!(schedule_at(1709164800), "0 0 0 29 2 * 2024");
+        // 2100 is not a leap year, february ends on the 28th
+        assert_eq!(schedule_at(4107456000), "0 0 0 28 2 * 2100");
+        assert_eq!(schedule_at(4107542400), "0 0 0 1 3 * 2100");
+    }
+
+    #[test]
+    fn schedule_at_year_end() {
+        assert_eq!(schedule_at(1704067199), "59 59 23 31 12 * 2023");
+        assert_eq!(schedule_at(1704067200), "0 0 0 1 1 * 2024");
+    }
+
+    #[test]
+    fn schedule_at_negative_timestamps() {
+        assert_eq!(schedule_at(-1), "59 59 23 31 12 * 1969");
+        assert_eq!(schedule_at(-31536000), "0 0 0 1 1 * 1969");
+        assert_eq!(schedule_at(-2208988800), "0 0 0 1 1 * 1900");
+    }
+
+    #[test]
+    fn schedule_daily_time_of_day() {
+        assert_eq!(schedule_daily(1704067199), "59 59 23 * * * *");
+        assert_eq!(schedule_daily(-1), "59 59 23 * * * *");
+    }
+}
@@ -1,7 +1,8 @@
 import * as anchor from "@project-serum/anchor";
-import { Program } from "@project-serum/anchor";
+import { Program, Spl } from "@project-serum/anchor";
 import { Socialprotocol } from "../target/types/socialprotocol";
 import { PublicKey, ComputeBudgetProgram } from "@solana/web3.js";
+import { assert } from "chai";
 
 describe("socialprotocol", () => {
   // Configure the client to use the local cluster.
@@ -12,6 +13,22 @@ describe("socialprotocol", () => {
 
   const program = anchor.workspace.Socialprotocol as Program<Socialprotocol>;
 
+  // profiles are seeded by user id, the wallet account maps a wallet to it
+  const userProfilePDA = async (wallet = provider.wallet.publicKey) => {
+    const [WalletPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("wallet"), wallet.toBuffer()],
+      program.programId
+    );
+    const { uid } = await program.account.wallet.fetch(WalletPDA);
+    const seed = Buffer.alloc(4);
+    seed.writeUInt32LE(uid);
+    const [UserProfilePDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("user_profile"), seed],
+      program.programId
+    );
+    return UserProfilePDA;
+  };
+
   it("Sets up spling", async () => {
     const [SplingPDA] = await PublicKey.findProgramAddress(
       [anchor.utils.bytes.utf8.encode("spling")],
@@ -27,6 +44,41 @@ describe("socialprotocol", () => {
       .rpc();
   });
 
+  it("Sets up config", async () => {
+    const [SplingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("spling")],
+      program.programId
+    );
+
+    const [ConfigPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("config")],
+      program.programId
+    );
+
+    const limit = {
+      window: new anchor.BN(60),
+      interval: new anchor.BN(1),
+      max: 10,
+    };
+
+    // only the upgrade authority of the program can set up the config
+    const [ProgramDataPDA] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    await program.methods
+      .setupConfig(limit, limit, limit, 2)
+      .accounts({
+        user: provider.wallet.publicKey,
+        spling: SplingPDA,
+        config: ConfigPDA,
+        program: program.programId,
+        programData: ProgramDataPDA,
+      })
+      .rpc();
+  });
+
   it("Creates a Bank", async () => {
     const [SplingPDA] = await PublicKey.findProgramAddress(
       [anchor.utils.bytes.utf8.encode("spling")],
@@ -59,9 +111,18 @@ describe("socialprotocol", () => {
       program.programId
     );
 
+    const spling = await program.account.spling.fetch(SplingPDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(spling.users + 1);
+
     const [UserProfilePDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("user_profile"), uid],
+      program.programId
+    );
+
+    const [WalletPDA] = await PublicKey.findProgramAddress(
       [
-        anchor.utils.bytes.utf8.encode("user_profile"),
+        anchor.utils.bytes.utf8.encode("wallet"),
         provider.wallet.publicKey.toBuffer(),
       ],
       program.programId
@@ -73,11 +134,12 @@ describe("socialprotocol", () => {
     let ta = new PublicKey("EwwRs2bCnStnB21QqHbBYMAusCTJ75o2Mepq9RJCEtos");
 
     await program.methods
-      .createUserProfile(shdw_public, null)
+      .createUserProfile(shdw_public, null, null)
       .accounts({
         user: provider.wallet.publicKey,
         spling: SplingPDA,
         userProfile: UserProfilePDA,
+        wallet: WalletPDA,
         b: BankPDA,
         // receiver: shdw_public,
         // senderTokenAccount: ta,
@@ -88,23 +150,422 @@ describe("socialprotocol", () => {
       .rpc();
   });
 
-  it("Sets up tags", async () => {
-    const [TagsPDA] = await PublicKey.findProgramAddress(
-      [anchor.utils.bytes.utf8.encode("tags")],
+  it("Creates an inbox", async () => {
+    const UserProfilePDA = await userProfilePDA();
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [InboxPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("inbox"), uid],
+      program.programId
+    );
+
+    await program.methods
+      .createInbox()
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        inbox: InboxPDA,
+      })
+      .rpc();
+
+    // nothing to read yet, so the cursor can't move past 0
+    await program.methods
+      .markRead(0)
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        inbox: InboxPDA,
+      })
+      .rpc();
+
+    const inbox = await program.account.inbox.fetch(InboxPDA);
+    assert.equal(inbox.uid, profile.uid);
+    assert.equal(inbox.count, 0);
+    assert.equal(inbox.read, 0);
+  });
+
+  it("Signs with a session key", async () => {
+    const UserProfilePDA = await userProfilePDA();
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [InboxPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("inbox"), uid],
+      program.programId
+    );
+
+    const session = anchor.web3.Keypair.generate();
+    const [SessionKeyPDA] = await PublicKey.findProgramAddress(
+      [
+        anchor.utils.bytes.utf8.encode("session_key"),
+        uid,
+        session.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    // only allowed to mark the inbox as read, for an hour, without spending
+    const SESSION_INBOX = 1 << 8;
+    const expiry = new anchor.BN(Math.floor(Date.now() / 1000) + 60 * 60);
+
+    await program.methods
+      .createSessionKey(
+        session.publicKey,
+        expiry,
+        SESSION_INBOX,
+        new anchor.BN(0),
+        new anchor.BN(10_000_000)
+      )
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        sessionKey: SessionKeyPDA,
+        sessionWallet: session.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .markRead(0)
+      .accounts({
+        user: session.publicKey,
+        userProfile: UserProfilePDA,
+        inbox: InboxPDA,
+      })
+      .remainingAccounts([
+        { pubkey: SessionKeyPDA, isWritable: false, isSigner: false },
+      ])
+      .signers([session])
+      .rpc();
+
+    await program.methods
+      .revokeSessionKey(session.publicKey)
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        sessionKey: SessionKeyPDA,
+      })
+      .rpc();
+
+    // without the session key account, the ephemeral key can't sign anymore
+    try {
+      await program.methods
+        .markRead(0)
+        .accounts({
+          user: session.publicKey,
+          userProfile: UserProfilePDA,
+          inbox: InboxPDA,
+        })
+        .signers([session])
+        .rpc();
+      assert.fail("signed after revoke");
+    } catch (err) {
+      assert.notEqual(err.message, "signed after revoke");
+    }
+  });
+
+  it("Publishes a messaging key", async () => {
+    const UserProfilePDA = await userProfilePDA();
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [MessagingKeyPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("messaging_key"), uid],
+      program.programId
+    );
+
+    // any 32 bytes will do for the program, clients use an X25519 public key
+    const key = Array.from(anchor.web3.Keypair.generate().publicKey.toBytes());
+
+    await program.methods
+      .setMessagingKey(key)
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        messagingKey: MessagingKeyPDA,
+      })
+      .rpc();
+
+    const messagingKey = await program.account.messagingKey.fetch(
+      MessagingKeyPDA
+    );
+    assert.deepEqual(messagingKey.key, key);
+  });
+
+  it("Sets up trending", async () => {
+    const [SplingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("spling")],
+      program.programId
+    );
+
+    const [TrendingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("trending")],
+      program.programId
+    );
+
+    await program.methods
+      .setupTrending()
+      .accounts({
+        user: provider.wallet.publicKey,
+        spling: SplingPDA,
+        trending: TrendingPDA,
+      })
+      .rpc();
+  });
+
+  it("Sets up and funds rewards", async () => {
+    const user = provider.wallet.publicKey;
+    const tokenProgram = Spl.token(provider);
+    const mint = anchor.web3.Keypair.generate();
+    const tokenAccount = anchor.web3.Keypair.generate();
+
+    const [ConfigPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("config")],
+      program.programId
+    );
+    const [RewardPoolPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward_pool")],
+      program.programId
+    );
+    const [RewardVaultPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("reward_vault")],
+      program.programId
+    );
+
+    await tokenProgram.methods
+      .initializeMint(0, user, null)
+      .accounts({ mint: mint.publicKey })
+      .preInstructions([
+        await tokenProgram.account.mint.createInstruction(mint),
+      ])
+      .signers([mint])
+      .rpc();
+
+    await tokenProgram.methods
+      .initializeAccount()
+      .accounts({
+        account: tokenAccount.publicKey,
+        mint: mint.publicKey,
+        authority: user,
+      })
+      .preInstructions([
+        await tokenProgram.account.token.createInstruction(tokenAccount),
+      ])
+      .signers([tokenAccount])
+      .rpc();
+
+    await tokenProgram.methods
+      .mintTo(new anchor.BN(1000))
+      .accounts({
+        mint: mint.publicKey,
+        to: tokenAccount.publicKey,
+        authority: user,
+      })
+      .rpc();
+
+    // 100 tokens per daily epoch
+    await program.methods
+      .setupRewards(new anchor.BN(100), new anchor.BN(24 * 60 * 60))
+      .accounts({
+        user,
+        config: ConfigPDA,
+        rewardPool: RewardPoolPDA,
+        rewardVault: RewardVaultPDA,
+        mint: mint.publicKey,
+        tokenProgram: tokenProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .fundRewards(new anchor.BN(1000))
+      .accounts({
+        user,
+        config: ConfigPDA,
+        rewardVault: RewardVaultPDA,
+        senderTokenAccount: tokenAccount.publicKey,
+        tokenProgram: tokenProgram.programId,
+      })
+      .rpc();
+
+    const vault = await provider.connection.getTokenAccountBalance(
+      RewardVaultPDA
+    );
+    assert.equal(vault.value.amount, "1000");
+  });
+
+  it("Stakes and unstakes", async () => {
+    const user = provider.wallet.publicKey;
+    const tokenProgram = Spl.token(provider);
+    const mint = anchor.web3.Keypair.generate();
+    const tokenAccount = anchor.web3.Keypair.generate();
+
+    const [ConfigPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("config")],
+      program.programId
+    );
+    const [StakeVaultPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("stake_vault")],
+      program.programId
+    );
+
+    const UserProfilePDA = await userProfilePDA();
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+    const [StakePositionPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("stake_position"), uid],
       program.programId
     );
 
+    await tokenProgram.methods
+      .initializeMint(0, user, null)
+      .accounts({ mint: mint.publicKey })
+      .preInstructions([
+        await tokenProgram.account.mint.createInstruction(mint),
+      ])
+      .signers([mint])
+      .rpc();
+
+    await tokenProgram.methods
+      .initializeAccount()
+      .accounts({
+        account: tokenAccount.publicKey,
+        mint: mint.publicKey,
+        authority: user,
+      })
+      .preInstructions([
+        await tokenProgram.account.token.createInstruction(tokenAccount),
+      ])
+      .signers([tokenAccount])
+      .rpc();
+
+    await tokenProgram.methods
+      .mintTo(new anchor.BN(1000))
+      .accounts({
+        mint: mint.publicKey,
+        to: tokenAccount.publicKey,
+        authority: user,
+      })
+      .rpc();
+
+    await program.methods
+      .setupStakeVault()
+      .accounts({
+        user,
+        config: ConfigPDA,
+        stakeVault: StakeVaultPDA,
+        mint: mint.publicKey,
+        tokenProgram: tokenProgram.programId,
+      })
+      .rpc();
+
+    // no cooldown, so the unstaked tokens can be withdrawn right away
+    await program.methods
+      .updateStaking({
+        cooldown: new anchor.BN(0),
+        boost: new anchor.BN(0),
+        subsidy: new anchor.BN(0),
+        tags: new anchor.BN(100),
+      })
+      .accounts({ user, config: ConfigPDA })
+      .rpc();
+
+    await program.methods
+      .stake(new anchor.BN(100))
+      .accounts({
+        user,
+        userProfile: UserProfilePDA,
+        stakePosition: StakePositionPDA,
+        stakeVault: StakeVaultPDA,
+        senderTokenAccount: tokenAccount.publicKey,
+        tokenProgram: tokenProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .unstake(new anchor.BN(40))
+      .accounts({
+        user,
+        userProfile: UserProfilePDA,
+        config: ConfigPDA,
+        stakePosition: StakePositionPDA,
+      })
+      .rpc();
+
+    await program.methods
+      .withdrawStake()
+      .accounts({
+        user,
+        userProfile: UserProfilePDA,
+        stakePosition: StakePositionPDA,
+        stakeVault: StakeVaultPDA,
+        tokenAccount: tokenAccount.publicKey,
+        tokenProgram: tokenProgram.programId,
+      })
+      .rpc();
+
+    const position = await program.account.stakePosition.fetch(
+      StakePositionPDA
+    );
+    assert.equal(position.amount.toNumber(), 60);
+    assert.equal(position.unstaking.toNumber(), 0);
+
+    const balance = await provider.connection.getTokenAccountBalance(
+      tokenAccount.publicKey
+    );
+    assert.equal(balance.value.amount, "940");
+
+    // a profile with staked tokens can't be deleted, so withdraw the rest
+    await program.methods
+      .unstake(new anchor.BN(60))
+      .accounts({
+        user,
+        userProfile: UserProfilePDA,
+        config: ConfigPDA,
+        stakePosition: StakePositionPDA,
+      })
+      .rpc();
+
+    await program.methods
+      .withdrawStake()
+      .accounts({
+        user,
+        userProfile: UserProfilePDA,
+        stakePosition: StakePositionPDA,
+        stakeVault: StakeVaultPDA,
+        tokenAccount: tokenAccount.publicKey,
+        tokenProgram: tokenProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Creates a tag", async () => {
     const [SplingPDA] = await PublicKey.findProgramAddress(
       [anchor.utils.bytes.utf8.encode("spling")],
       program.programId
     );
 
+    const UserProfilePDA = await userProfilePDA();
+
+    const [TagPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("tag"), Buffer.from("hello")],
+      program.programId
+    );
+
     await program.methods
-      .setupTags()
+      .createTag("hello")
       .accounts({
         user: provider.wallet.publicKey,
         spling: SplingPDA,
-        tags: TagsPDA,
+        userProfile: UserProfilePDA,
+        tag: TagPDA,
       })
       .rpc();
   });
@@ -115,24 +576,33 @@ describe("socialprotocol", () => {
       program.programId
     );
 
-    const [TagsPDA] = await PublicKey.findProgramAddress(
-      [anchor.utils.bytes.utf8.encode("tags")],
+    const [TagPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("tag"), Buffer.from("hello")],
       program.programId
     );
 
-    const [BankPDA] = await PublicKey.findProgramAddress(
-      [anchor.utils.bytes.utf8.encode("b")],
+    const [TrendingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("trending")],
       program.programId
     );
 
-    const [UserProfilePDA] = await PublicKey.findProgramAddress(
-      [
-        anchor.utils.bytes.utf8.encode("user_profile"),
-        provider.wallet.publicKey.toBuffer(),
-      ],
+    // the post index entry of the tag is seeded by its index counter
+    const tag = await program.account.tag.fetch(TagPDA);
+    const index = Buffer.alloc(4);
+    index.writeUInt32LE(tag.next);
+
+    const [TagPostPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("tag_post"), TagPDA.toBuffer(), index],
+      program.programId
+    );
+
+    const [BankPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("b")],
       program.programId
     );
 
+    const UserProfilePDA = await userProfilePDA();
+
     const [PostPDA] = await PublicKey.findProgramAddress(
       [anchor.utils.bytes.utf8.encode("post"), shdw.publicKey.toBuffer()],
       program.programId
@@ -143,19 +613,40 @@ describe("socialprotocol", () => {
       program.programId
     );
 
+    const [ConfigPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("config")],
+      program.programId
+    );
+
+    const userProfile = await program.account.userProfile.fetch(
+      UserProfilePDA
+    );
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(userProfile.uid);
+
+    const [RateLimitPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("rate_limit"), uid],
+      program.programId
+    );
+
+    // delete the post after two hours
+    const expiry = new anchor.BN(Math.floor(Date.now() / 1000) + 2 * 60 * 60);
+
     const postThread = PublicKey.findProgramAddressSync(
       [Buffer.from("thread"), PostPDA.toBuffer(), Buffer.from("post_thread")],
       new PublicKey("3XXuUFfweXBwFgFfYaejLvZE4cGZiHgKiGfMtdxNzYmv")
     )[0];
 
     await program.methods
-      .submitPost(1, shdw.publicKey, "hello", null, "*/55 * * * * * *")
+      .submitPost(1, shdw.publicKey, ["hello"], [], null, expiry)
       .accounts({
         user: provider.wallet.publicKey,
         spling: SplingPDA,
         userProfile: UserProfilePDA,
+        config: ConfigPDA,
+        rateLimit: RateLimitPDA,
         post: PostPDA,
-        tags: TagsPDA,
+        trending: TrendingPDA,
         likes: LikesPDA,
         b: BankPDA,
         postThread,
@@ -169,8 +660,1877 @@ describe("socialprotocol", () => {
         // mint: ta,
         // token_program: shdw_public,
       })
+      .remainingAccounts([
+        { pubkey: TagPDA, isWritable: true, isSigner: false },
+        { pubkey: TagPostPDA, isWritable: true, isSigner: false },
+      ])
       .rpc();
 
     console.log(postThread.toBase58());
   });
+
+  it("Reports trending tags", async () => {
+    const [TrendingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("trending")],
+      program.programId
+    );
+
+    const tags = await program.methods
+      .getTrendingTags(5)
+      .accounts({ trending: TrendingPDA })
+      .view();
+
+    assert.isAtLeast(tags.length, 1);
+  });
+
+  it("Bookmarks a post and collects it", async () => {
+    const UserProfilePDA = await userProfilePDA();
+
+    const [PostPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("post"), shdw.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [BookmarkPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("bookmark"), uid, PostPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .bookmarkPost()
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        post: PostPDA,
+        bookmark: BookmarkPDA,
+      })
+      .rpc();
+
+    const [CollectionPDA] = await PublicKey.findProgramAddress(
+      [
+        anchor.utils.bytes.utf8.encode("collection"),
+        uid,
+        Buffer.from("reading list"),
+      ],
+      program.programId
+    );
+
+    // visibility 2 is private
+    await program.methods
+      .createCollection("reading list", 2)
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        collection: CollectionPDA,
+      })
+      .rpc();
+
+    await program.methods
+      .addToCollection("reading list")
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        post: PostPDA,
+        collection: CollectionPDA,
+      })
+      .rpc();
+
+    const collection = await program.account.collection.fetch(CollectionPDA);
+    assert.equal(collection.posts.length, 1);
+    assert.ok(collection.posts[0].equals(PostPDA));
+
+    await program.methods
+      .removeBookmark()
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        post: PostPDA,
+        bookmark: BookmarkPDA,
+      })
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(BookmarkPDA));
+  });
+
+  it("Curates a list", async () => {
+    const [SplingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("spling")],
+      program.programId
+    );
+
+    const UserProfilePDA = await userProfilePDA();
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [ListPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("list"), uid, Buffer.from("solana devs")],
+      program.programId
+    );
+
+    const [ListMemberPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("list_member"), ListPDA.toBuffer(), uid],
+      program.programId
+    );
+
+    // visibility 1 is public
+    await program.methods
+      .createList("solana devs", 1)
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        list: ListPDA,
+      })
+      .rpc();
+
+    await program.methods
+      .addToList("solana devs", profile.uid)
+      .accounts({
+        user: provider.wallet.publicKey,
+        spling: SplingPDA,
+        userProfile: UserProfilePDA,
+        list: ListPDA,
+        listMember: ListMemberPDA,
+      })
+      .rpc();
+
+    let list = await program.account.list.fetch(ListPDA);
+    assert.equal(list.members, 1);
+
+    await program.methods
+      .removeFromList("solana devs", profile.uid)
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        list: ListPDA,
+        listMember: ListMemberPDA,
+      })
+      .rpc();
+
+    await program.methods
+      .deleteList("solana devs")
+      .accounts({
+        user: provider.wallet.publicKey,
+        userProfile: UserProfilePDA,
+        list: ListPDA,
+      })
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(ListPDA));
+  });
+
+  it("Unsubscribes from a list created again", async () => {
+    const UserProfilePDA = await userProfilePDA();
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [ListPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("list"), uid, Buffer.from("rust devs")],
+      program.programId
+    );
+
+    const [ListSubscriptionPDA] = await PublicKey.findProgramAddress(
+      [
+        anchor.utils.bytes.utf8.encode("list_subscription"),
+        ListPDA.toBuffer(),
+        uid,
+      ],
+      program.programId
+    );
+
+    const accounts = {
+      user: provider.wallet.publicKey,
+      userProfile: UserProfilePDA,
+      list: ListPDA,
+    };
+
+    await program.methods.createList("rust devs", 1).accounts(accounts).rpc();
+    await program.methods
+      .subscribeList()
+      .accounts({ ...accounts, listSubscription: ListSubscriptionPDA })
+      .rpc();
+    await program.methods.deleteList("rust devs").accounts(accounts).rpc();
+
+    // the new list has a later creation timestamp
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    await program.methods.createList("rust devs", 1).accounts(accounts).rpc();
+
+    // the old subscription doesn't count towards the new list
+    await program.methods
+      .unsubscribeList()
+      .accounts({ ...accounts, listSubscription: ListSubscriptionPDA })
+      .rpc();
+
+    const list = await program.account.list.fetch(ListPDA);
+    assert.equal(list.subscribers, 0);
+
+    await program.methods.deleteList("rust devs").accounts(accounts).rpc();
+  });
+
+  it("Protects an account", async () => {
+    const UserProfilePDA = await userProfilePDA();
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [ProfileSettingsPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("profile_settings"), uid],
+      program.programId
+    );
+
+    for (const protect of [true, false]) {
+      await program.methods
+        .setProtected(protect)
+        .accounts({
+          user: provider.wallet.publicKey,
+          userProfile: UserProfilePDA,
+          profileSettings: ProfileSettingsPDA,
+        })
+        .rpc();
+
+      const settings = await program.account.profileSettings.fetch(
+        ProfileSettingsPDA
+      );
+      assert.equal(settings.protected, protect);
+    }
+  });
+
+  it("Adds, rotates and removes a wallet", async () => {
+    const UserProfilePDA = await userProfilePDA();
+    const user = provider.wallet.publicKey;
+    const second = anchor.web3.Keypair.generate();
+
+    const [WalletPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("wallet"), second.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .addWallet()
+      .accounts({
+        user,
+        newWallet: second.publicKey,
+        userProfile: UserProfilePDA,
+        wallet: WalletPDA,
+      })
+      .signers([second])
+      .rpc();
+
+    assert.ok((await userProfilePDA(second.publicKey)).equals(UserProfilePDA));
+
+    // the primary wallet hands over to the second wallet
+    await program.methods
+      .rotatePrimaryWallet(second.publicKey)
+      .accounts({ user, userProfile: UserProfilePDA })
+      .rpc();
+
+    let profile = await program.account.userProfile.fetch(UserProfilePDA);
+    assert.ok(profile.user.equals(second.publicKey));
+
+    // a secondary wallet can't take over, lost keys are recovered by guardians
+    try {
+      await program.methods
+        .rotatePrimaryWallet(user)
+        .accounts({ user, userProfile: UserProfilePDA })
+        .rpc();
+      assert.fail("secondary wallet took over");
+    } catch (err) {
+      assert.notEqual(err.message, "secondary wallet took over");
+    }
+
+    await program.methods
+      .rotatePrimaryWallet(user)
+      .accounts({ user: second.publicKey, userProfile: UserProfilePDA })
+      .signers([second])
+      .rpc();
+
+    profile = await program.account.userProfile.fetch(UserProfilePDA);
+    assert.ok(profile.user.equals(user));
+
+    await program.methods
+      .removeWallet(second.publicKey)
+      .accounts({ user, userProfile: UserProfilePDA, wallet: WalletPDA })
+      .rpc();
+
+    profile = await program.account.userProfile.fetch(UserProfilePDA);
+    assert.ok(profile.wallets.every((w) => w.equals(PublicKey.default)));
+  });
+
+  it("Recovers with guardians, until the owner cancels", async () => {
+    const UserProfilePDA = await userProfilePDA();
+    const user = provider.wallet.publicKey;
+    const guardian = anchor.web3.Keypair.generate();
+    const newWallet = anchor.web3.Keypair.generate().publicKey;
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [GuardiansPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("guardians"), uid],
+      program.programId
+    );
+
+    // the guardian pays for its approval, and a profile of its own
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: user,
+          toPubkey: guardian.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+
+    await program.methods
+      .setGuardians(
+        [{ uid: 0, wallet: guardian.publicKey }],
+        1,
+        new anchor.BN(24 * 60 * 60)
+      )
+      .accounts({ user, userProfile: UserProfilePDA, guardians: GuardiansPDA })
+      .rpc();
+
+    const index = Buffer.alloc(4);
+    index.writeUInt32LE(0);
+    const [RecoveryPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("recovery"), uid, index],
+      program.programId
+    );
+    const [ApprovalPDA] = await PublicKey.findProgramAddress(
+      [
+        anchor.utils.bytes.utf8.encode("recovery_approval"),
+        RecoveryPDA.toBuffer(),
+        Buffer.from([0]),
+      ],
+      program.programId
+    );
+
+    const recoveryWallet = (wallet: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [anchor.utils.bytes.utf8.encode("wallet"), wallet.toBuffer()],
+        program.programId
+      )[0];
+
+    // a wallet of another profile can't become the primary wallet
+    const [SplingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("spling")],
+      program.programId
+    );
+    const [BankPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("b")],
+      program.programId
+    );
+    const spling = await program.account.spling.fetch(SplingPDA);
+    const otherUid = Buffer.alloc(4);
+    otherUid.writeUInt32LE(spling.users + 1);
+    await program.methods
+      .createUserProfile(anchor.web3.Keypair.generate().publicKey, null, null)
+      .accounts({
+        user: guardian.publicKey,
+        spling: SplingPDA,
+        userProfile: PublicKey.findProgramAddressSync(
+          [anchor.utils.bytes.utf8.encode("user_profile"), otherUid],
+          program.programId
+        )[0],
+        wallet: recoveryWallet(guardian.publicKey),
+        b: BankPDA,
+      })
+      .signers([guardian])
+      .rpc();
+    try {
+      await program.methods
+        .initiateRecovery(0, guardian.publicKey)
+        .accounts({
+          user: guardian.publicKey,
+          userProfile: UserProfilePDA,
+          guardians: GuardiansPDA,
+          recovery: RecoveryPDA,
+          recoveryWallet: recoveryWallet(guardian.publicKey),
+        })
+        .signers([guardian])
+        .rpc();
+      assert.fail("recovered to a wallet of another profile");
+    } catch (err) {
+      assert.include(err.message, "InvalidRecoveryWallet");
+    }
+
+    await program.methods
+      .initiateRecovery(0, newWallet)
+      .accounts({
+        user: guardian.publicKey,
+        userProfile: UserProfilePDA,
+        guardians: GuardiansPDA,
+        recovery: RecoveryPDA,
+        recoveryWallet: recoveryWallet(newWallet),
+      })
+      .signers([guardian])
+      .rpc();
+
+    await program.methods
+      .approveRecovery(0)
+      .accounts({
+        user: guardian.publicKey,
+        guardians: GuardiansPDA,
+        recovery: RecoveryPDA,
+        approval: ApprovalPDA,
+      })
+      .signers([guardian])
+      .rpc();
+
+    let recovery = await program.account.recovery.fetch(RecoveryPDA);
+    assert.equal(recovery.approvals, 1);
+    assert.notEqual(recovery.approvedAt.toNumber(), 0);
+
+    // only a recovery that wasn't approved or executed in time can be cancelled
+    // by others
+    try {
+      await program.methods
+        .cancelRecovery()
+        .accounts({
+          user: guardian.publicKey,
+          userProfile: UserProfilePDA,
+          guardians: GuardiansPDA,
+          recovery: RecoveryPDA,
+        })
+        .signers([guardian])
+        .rpc();
+      assert.fail("cancelled an approved recovery");
+    } catch (err) {
+      assert.include(err.message, "NotAuthorized");
+    }
+
+    // the delay has not passed, so the owner can still cancel
+    await program.methods
+      .cancelRecovery()
+      .accounts({
+        user,
+        userProfile: UserProfilePDA,
+        guardians: GuardiansPDA,
+        recovery: RecoveryPDA,
+      })
+      .rpc();
+
+    recovery = await program.account.recovery.fetch(RecoveryPDA);
+    assert.equal(recovery.status, 2);
+    const guardians = await program.account.guardians.fetch(GuardiansPDA);
+    assert.isFalse(guardians.pending);
+  });
+
+  it("Attests and revokes a verified user", async () => {
+    const UserProfilePDA = await userProfilePDA();
+    const user = provider.wallet.publicKey;
+    const verified = 1;
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [ConfigPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("config")],
+      program.programId
+    );
+    const [IssuerPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("issuer"), user.toBuffer()],
+      program.programId
+    );
+    const [AttestationPDA] = await PublicKey.findProgramAddress(
+      [
+        anchor.utils.bytes.utf8.encode("attestation"),
+        uid,
+        Buffer.from([verified]),
+      ],
+      program.programId
+    );
+
+    // the admin registers itself as an issuer of verifications
+    await program.methods
+      .setIssuer(user, 1 << verified)
+      .accounts({ user, config: ConfigPDA, issuer: IssuerPDA })
+      .rpc();
+
+    await program.methods
+      .issueAttestation(profile.uid, verified, new anchor.BN(0))
+      .accounts({
+        user,
+        issuer: IssuerPDA,
+        userProfile: UserProfilePDA,
+        attestation: AttestationPDA,
+      })
+      .rpc();
+
+    let attestation = await program.account.attestation.fetch(AttestationPDA);
+    assert.ok(attestation.issuer.equals(user));
+    assert.isFalse(attestation.revoked);
+
+    await program.methods
+      .revokeAttestation(profile.uid, verified)
+      .accounts({ user, config: ConfigPDA, attestation: AttestationPDA })
+      .rpc();
+
+    attestation = await program.account.attestation.fetch(AttestationPDA);
+    assert.isTrue(attestation.revoked);
+    assert.isTrue(attestation.adminRevoked);
+
+    // issuing it again would undo the revocation of the admin
+    try {
+      await program.methods
+        .issueAttestation(profile.uid, verified, new anchor.BN(0))
+        .accounts({
+          user,
+          issuer: IssuerPDA,
+          userProfile: UserProfilePDA,
+          attestation: AttestationPDA,
+        })
+        .rpc();
+      assert.fail("issued a revoked attestation again");
+    } catch (err) {
+      assert.include(err.message, "AttestationRevokedByAdmin");
+    }
+
+    // closing the attestation lifts the revocation of the admin
+    await program.methods
+      .closeAttestation(profile.uid, verified)
+      .accounts({ user, config: ConfigPDA, attestation: AttestationPDA })
+      .rpc();
+
+    await program.methods
+      .issueAttestation(profile.uid, verified, new anchor.BN(0))
+      .accounts({
+        user,
+        issuer: IssuerPDA,
+        userProfile: UserProfilePDA,
+        attestation: AttestationPDA,
+      })
+      .rpc();
+
+    attestation = await program.account.attestation.fetch(AttestationPDA);
+    assert.isFalse(attestation.revoked);
+    assert.isFalse(attestation.adminRevoked);
+  });
+
+  it("Moderates a post and restores it", async () => {
+    const UserProfilePDA = await userProfilePDA();
+    const user = provider.wallet.publicKey;
+
+    const [ConfigPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("config")],
+      program.programId
+    );
+    const [PostPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("post"), shdw.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // no boost and a low subsidy threshold, so the later tests are unaffected
+    await program.methods
+      .updateReputation({
+        like: new anchor.BN(1),
+        reply: new anchor.BN(2),
+        age: new anchor.BN(0),
+        moderation: new anchor.BN(10),
+        boost: new anchor.BN(0),
+        subsidy: new anchor.BN(-1000),
+        group: new anchor.BN(0),
+      })
+      .accounts({ user, config: ConfigPDA })
+      .rpc();
+
+    const [TrendingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("trending")],
+      program.programId
+    );
+    const [TagPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("tag"), Buffer.from("hello")],
+      program.programId
+    );
+
+    // the tag of the post is passed in the remaining accounts
+    const moderate = (remove: boolean) =>
+      program.methods
+        .moderatePost(remove)
+        .accounts({
+          user,
+          config: ConfigPDA,
+          post: PostPDA,
+          trending: TrendingPDA,
+          authorProfile: UserProfilePDA,
+        })
+        .remainingAccounts([
+          { pubkey: TagPDA, isWritable: true, isSigner: false },
+        ])
+        .rpc();
+
+    const before = await program.account.userProfile.fetch(UserProfilePDA);
+    const tag = await program.account.tag.fetch(TagPDA);
+
+    await moderate(true);
+    let post = await program.account.post.fetch(PostPDA);
+    let profile = await program.account.userProfile.fetch(UserProfilePDA);
+    assert.equal(post.st, 3);
+    assert.equal(
+      profile.reputation.toNumber(),
+      before.reputation.toNumber() - 10
+    );
+
+    // a removed post no longer counts for its tags
+    let after = await program.account.tag.fetch(TagPDA);
+    assert.equal(after.posts, tag.posts - 1);
+
+    // restoring after an appeal gives the reputation back
+    await moderate(false);
+    post = await program.account.post.fetch(PostPDA);
+    profile = await program.account.userProfile.fetch(UserProfilePDA);
+    assert.equal(post.st, 1);
+    assert.equal(profile.reputation.toNumber(), before.reputation.toNumber());
+    after = await program.account.tag.fetch(TagPDA);
+    assert.equal(after.posts, tag.posts);
+  });
+
+  it("Awards a badge and mints it", async () => {
+    const UserProfilePDA = await userProfilePDA();
+    const user = provider.wallet.publicKey;
+    const firstPost = 1;
+
+    const profile = await program.account.userProfile.fetch(UserProfilePDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(profile.uid);
+
+    const [BadgePDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("badge"), uid, Buffer.from([firstPost])],
+      program.programId
+    );
+    const [BadgeMintPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("badge_mint"), Buffer.from([firstPost])],
+      program.programId
+    );
+    const [TokenAccountPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("badge_token"), BadgePDA.toBuffer()],
+      program.programId
+    );
+
+    // the user submitted a post earlier, so the badge is earned
+    await program.methods
+      .awardBadge(profile.uid, firstPost)
+      .accounts({ user, userProfile: UserProfilePDA, badge: BadgePDA })
+      .rpc();
+
+    await program.methods
+      .mintBadge(firstPost)
+      .accounts({
+        user,
+        userProfile: UserProfilePDA,
+        badge: BadgePDA,
+        badgeMint: BadgeMintPDA,
+        tokenAccount: TokenAccountPDA,
+        tokenProgram: Spl.token(provider).programId,
+      })
+      .rpc();
+
+    const badge = await program.account.badge.fetch(BadgePDA);
+    assert.isTrue(badge.minted);
+    const token = await provider.connection.getTokenAccountBalance(
+      TokenAccountPDA
+    );
+    assert.equal(token.value.amount, "1");
+  });
+
+  it("Refers a new user", async () => {
+    const UserProfilePDA = await userProfilePDA();
+    const referee = anchor.web3.Keypair.generate();
+
+    const [SplingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("spling")],
+      program.programId
+    );
+    const [BankPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("b")],
+      program.programId
+    );
+
+    const spling = await program.account.spling.fetch(SplingPDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(spling.users + 1);
+
+    const [RefereeProfilePDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("user_profile"), uid],
+      program.programId
+    );
+    const [WalletPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("wallet"), referee.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // the new user pays for its own profile
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: referee.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+
+    const referrer = await program.account.userProfile.fetch(UserProfilePDA);
+
+    await program.methods
+      .createUserProfile(
+        anchor.web3.Keypair.generate().publicKey,
+        null,
+        referrer.uid
+      )
+      .accounts({
+        user: referee.publicKey,
+        spling: SplingPDA,
+        userProfile: RefereeProfilePDA,
+        wallet: WalletPDA,
+        b: BankPDA,
+      })
+      .remainingAccounts([
+        { pubkey: UserProfilePDA, isWritable: true, isSigner: false },
+      ])
+      .signers([referee])
+      .rpc();
+
+    const profile = await program.account.userProfile.fetch(RefereeProfilePDA);
+    assert.equal(profile.referrer, referrer.uid);
+    const after = await program.account.userProfile.fetch(UserProfilePDA);
+    assert.equal(after.referrals, referrer.referrals + 1);
+  });
+
+  it("Subscribes to a creator and cancels", async () => {
+    const user = provider.wallet.publicKey;
+    const subscriber = anchor.web3.Keypair.generate();
+    const tokenProgram = Spl.token(provider);
+    const mint = anchor.web3.Keypair.generate();
+    const creatorTokenAccount = anchor.web3.Keypair.generate();
+    const tokenAccount = anchor.web3.Keypair.generate();
+
+    const [SplingPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("spling")],
+      program.programId
+    );
+    const [BankPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("b")],
+      program.programId
+    );
+
+    const UserProfilePDA = await userProfilePDA();
+    const creator = await program.account.userProfile.fetch(UserProfilePDA);
+    const creatorUid = Buffer.alloc(4);
+    creatorUid.writeUInt32LE(creator.uid);
+
+    const spling = await program.account.spling.fetch(SplingPDA);
+    const uid = Buffer.alloc(4);
+    uid.writeUInt32LE(spling.users + 1);
+
+    const [SubscriberProfilePDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("user_profile"), uid],
+      program.programId
+    );
+    const [WalletPDA] = await PublicKey.findProgramAddress(
+      [
+        anchor.utils.bytes.utf8.encode("wallet"),
+        subscriber.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const [SubscriptionTierPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("subscription_tier"), creatorUid],
+      program.programId
+    );
+    const [SubscriptionPDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("subscription"), creatorUid, uid],
+      program.programId
+    );
+    const [DelegatePDA] = await PublicKey.findProgramAddress(
+      [anchor.utils.bytes.utf8.encode("subscription_delegate")],
+      program.programId
+    );
+
+    const threadProgram = new PublicKey(
+      "3XXuUFfweXBwFgFfYaejLvZE4cGZiHgKiGfMtdxNzYmv"
+    );
+    const [SubscriptionThread] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("thread"),
+        SubscriptionPDA.toBuffer(),
+        Buffer.from("subscription_thread"),
+      ],
+      threadProgram
+    );
+
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: user,
+          toPubkey: subscriber.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+
+    await program.methods
+      .createUserProfile(anchor.web3.Keypair.generate().publicKey, null, null)
+      .accounts({
+        user: subscriber.publicKey,
+        spling: SplingPDA,
+        userProfile: SubscriberProfilePDA,
+        wallet: WalletPDA,
+        b: BankPDA,
+      })
+      .signers([subscriber])
+      .rpc();
+
+    await tokenProgram.methods
+      .initializeMint(0, user, null)
+      .accounts({ mint: mint.publicKey })
+      .preInstructions([
+        await tokenProgram.account.mint.createInstruction(mint),
+      ])
+      .signers([mint])
+      .rpc();
+
+    for (const [account, authority] of [
+      [creatorTokenAccount, user],
+      [tokenAccount, subscriber.publicKey],
+    ] as const) {
+      await tokenProgram.methods
+        .initializeAccount()
+        .accounts({
+          account: account.publicKey,
+          mint: mint.publicKey,
+          authority,
+        })
+        .preInstructions([
+          await tokenProgram.account.token.createInstruction(account),
+        ])
+        .signers([account])
+        .rpc();
+    }
+
+    await tokenProgram.methods
+      .mintTo(new anchor.BN(1000))
+      .accounts({
+        mint: mint.publicKey,
+        to: tokenAccount.publicKey,
+        authority: user,
+      })
+      .rpc();
+
+    // 100 tokens every 30 days
+    await program.methods
+      .setSubscriptionTier(new anchor.BN(100), new anchor.BN(30 * 86400))
+      .accounts({
+        user,
+        userProfile: UserProfilePDA,
+        subscriptionTier: SubscriptionTierPDA,
+        tokenAccount: creatorTokenAccount.publicKey,
+      })
+      .rpc();
+
+    // approve the next 3 periods, and fund the thread
+    await program.methods
+      .subscribe(3, new anchor.BN(10_000_000))
+      .accounts({
+        user: subscriber.publicKey,
+        userProfile: SubscriberProfilePDA,
+        subscriptionTier: SubscriptionTierPDA,
+        subscription: SubscriptionPDA,
+        tokenAccount: tokenAccount.publicKey,
+        recipient: creatorTokenAccount.publicKey,
+        delegate: DelegatePDA,
+        subscriptionThread: SubscriptionThread,
+        payer: subscriber.publicKey,
+        threadProgram,
+        tokenProgram: tokenProgram.programId,
+      })
+      .signers([subscriber])
+      .rpc();
+
+    const balance = await provider.connection.getTokenAccountBalance(
+      creatorTokenAccount.publicKey
+    );
+    assert.equal(balance.value.amount, "100");
+
+    const subscription = await program.account.subscription.fetch(
+      SubscriptionPDA
+    );
+    assert.equal(subscription.allowance.toNumber(), 300);
+    assert.equal(subscription.status, 1);
+
+    await program.methods
+      .cancelSubscription()
+      .accounts({
+        user: subscriber.publicKey,
+        userProfile: SubscriberProfilePDA,
+        subscription: SubscriptionPDA,
+        subscriptionTier: SubscriptionTierPDA,
+        payer: subscriber.publicKey,
+        subscriptionThread: SubscriptionThread,
+        threadProgram,
+      })
+      .signers([subscriber])
+      .rpc();
+
+    // still active until the end of the paid period
+    const cancelled = await program.account.subscription.fetch(
+      SubscriptionPDA
+    );
+    assert.equal(cancelled.status, 2);
+    assert.isAbove(cancelled.paidUntil.toNumber(), Date.now() / 1000);
+
+    // subscribing again continues the paid period, without paying twice
+    await program.methods
+      .subscribe(3, new anchor.BN(10_000_000))
+      .accounts({
+        user: subscriber.publicKey,
+        userProfile: SubscriberProfilePDA,
+        subscriptionTier: SubscriptionTierPDA,
+        subscription: SubscriptionPDA,
+        tokenAccount: tokenAccount.publicKey,
+        recipient: creatorTokenAccount.publicKey,
+        delegate: DelegatePDA,
+        subscriptionThread: SubscriptionThread,
+        payer: subscriber.publicKey,
+        threadProgram,
+        tokenProgram: tokenProgram.programId,
+      })
+      .signers([subscriber])
+      .rpc();
+
+    const renewed = await program.account.subscription.fetch(SubscriptionPDA);
+    assert.equal(renewed.status, 1);
+    assert.equal(renewed.paidUntil.toNumber(), cancelled.paidUntil.toNumber());
+    const after = await provider.connection.getTokenAccountBalance(
+      creatorTokenAccount.publicKey
+    );
+    assert.equal(after.value.amount, "100");
+  });
+
+  describe("polls", () => {
+    const user = provider.wallet.publicKey;
+    const tokenProgram = Spl.token(provider);
+    const mint = anchor.web3.Keypair.generate();
+    const pollShdw = anchor.web3.Keypair.generate().publicKey;
+
+    const pda = (...seeds: (Buffer | Uint8Array)[]) =>
+      PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+    const SplingPDA = pda(anchor.utils.bytes.utf8.encode("spling"));
+    const ConfigPDA = pda(anchor.utils.bytes.utf8.encode("config"));
+    let UserProfilePDA: PublicKey;
+    const PostPDA = pda(
+      anchor.utils.bytes.utf8.encode("post"),
+      pollShdw.toBuffer()
+    );
+    const LikesPDA = pda(
+      anchor.utils.bytes.utf8.encode("likes"),
+      PostPDA.toBuffer()
+    );
+    const PollPDA = pda(
+      anchor.utils.bytes.utf8.encode("poll"),
+      PostPDA.toBuffer()
+    );
+
+    const threadProgram = new PublicKey(
+      "3XXuUFfweXBwFgFfYaejLvZE4cGZiHgKiGfMtdxNzYmv"
+    );
+    const pollThread = PublicKey.findProgramAddressSync(
+      [Buffer.from("thread"), PostPDA.toBuffer(), Buffer.from("poll_thread")],
+      threadProgram
+    )[0];
+
+    const uid = async () => {
+      const profile = await program.account.userProfile.fetch(UserProfilePDA);
+      const uid = Buffer.alloc(4);
+      uid.writeUInt32LE(profile.uid);
+      return uid;
+    };
+
+    before(async () => {
+      UserProfilePDA = await userProfilePDA();
+
+      await tokenProgram.methods
+        .initializeMint(0, user, null)
+        .accounts({ mint: mint.publicKey })
+        .preInstructions([
+          await tokenProgram.account.mint.createInstruction(mint),
+        ])
+        .signers([mint])
+        .rpc();
+    });
+
+    it("Submits a poll and votes once", async () => {
+      // no close timestamp, so no thread is created
+      await program.methods
+        .submitPoll(0, pollShdw, 3, null, false)
+        .accounts({
+          user,
+          spling: SplingPDA,
+          userProfile: UserProfilePDA,
+          config: ConfigPDA,
+          rateLimit: pda(
+            anchor.utils.bytes.utf8.encode("rate_limit"),
+            await uid()
+          ),
+          post: PostPDA,
+          likes: LikesPDA,
+          poll: PollPDA,
+          mint: mint.publicKey,
+          pollThread,
+          threadProgram,
+        })
+        .rpc();
+
+      const PollVotePDA = pda(
+        anchor.utils.bytes.utf8.encode("poll_vote"),
+        PostPDA.toBuffer(),
+        await uid()
+      );
+
+      await program.methods
+        .votePoll(1)
+        .accounts({
+          user,
+          userProfile: UserProfilePDA,
+          post: PostPDA,
+          poll: PollPDA,
+          pollVote: PollVotePDA,
+        })
+        .rpc();
+
+      const poll = await program.account.poll.fetch(PollPDA);
+      assert.equal(poll.voters, 1);
+      assert.equal(poll.tallies[1].toNumber(), 1);
+
+      // the vote account already exists, so a second vote fails
+      try {
+        await program.methods
+          .votePoll(2)
+          .accounts({
+            user,
+            userProfile: UserProfilePDA,
+            post: PostPDA,
+            poll: PollPDA,
+            pollVote: PollVotePDA,
+          })
+          .rpc();
+        assert.fail("voted twice");
+      } catch (err) {
+        assert.notEqual(err.message, "voted twice");
+      }
+    });
+
+    it("Needs a close timestamp to weigh votes by stake", async () => {
+      // the stake of voters is locked until the poll closes, so it must close
+      const shdw = anchor.web3.Keypair.generate().publicKey;
+      const post = pda(anchor.utils.bytes.utf8.encode("post"), shdw.toBuffer());
+      try {
+        await program.methods
+          .submitPoll(0, shdw, 3, null, true)
+          .accounts({
+            user,
+            spling: SplingPDA,
+            userProfile: UserProfilePDA,
+            config: ConfigPDA,
+            rateLimit: pda(
+              anchor.utils.bytes.utf8.encode("rate_limit"),
+              await uid()
+            ),
+            post,
+            likes: pda(
+              anchor.utils.bytes.utf8.encode("likes"),
+              post.toBuffer()
+            ),
+            poll: pda(anchor.utils.bytes.utf8.encode("poll"), post.toBuffer()),
+            mint: mint.publicKey,
+            pollThread: PublicKey.findProgramAddressSync(
+              [
+                Buffer.from("thread"),
+                post.toBuffer(),
+                Buffer.from("poll_thread"),
+              ],
+              threadProgram
+            )[0],
+            threadProgram,
+          })
+          .rpc();
+        assert.fail("weighted poll without close");
+      } catch (err) {
+        assert.include(err.message, "WeightedPollNeverCloses");
+      }
+    });
+  });
+
+  describe("returns rent to the payer on delete", () => {
+    const user = provider.wallet.publicKey;
+    const tokenProgram = Spl.token(provider);
+    const mint = anchor.web3.Keypair.generate();
+    const tokenAccount = anchor.web3.Keypair.generate();
+    const postShdw = anchor.web3.Keypair.generate().publicKey;
+    const replyShdw = anchor.web3.Keypair.generate().publicKey;
+
+    const pda = (...seeds: (Buffer | Uint8Array)[]) =>
+      PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+    const SplingPDA = pda(anchor.utils.bytes.utf8.encode("spling"));
+    const ConfigPDA = pda(anchor.utils.bytes.utf8.encode("config"));
+    const BankPDA = pda(anchor.utils.bytes.utf8.encode("b"));
+    const TrendingPDA = pda(anchor.utils.bytes.utf8.encode("trending"));
+    const RewardPoolPDA = pda(anchor.utils.bytes.utf8.encode("reward_pool"));
+    let UserProfilePDA: PublicKey;
+    const GroupProfilePDA = pda(
+      anchor.utils.bytes.utf8.encode("group_profile"),
+      user.toBuffer()
+    );
+    const PostPDA = pda(
+      anchor.utils.bytes.utf8.encode("post"),
+      postShdw.toBuffer()
+    );
+    const LikesPDA = pda(
+      anchor.utils.bytes.utf8.encode("likes"),
+      PostPDA.toBuffer()
+    );
+    const ReplyPDA = pda(
+      anchor.utils.bytes.utf8.encode("reply"),
+      replyShdw.toBuffer()
+    );
+
+    const threadProgram = new PublicKey(
+      "3XXuUFfweXBwFgFfYaejLvZE4cGZiHgKiGfMtdxNzYmv"
+    );
+    const thread = (name: string) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("thread"), PostPDA.toBuffer(), Buffer.from(name)],
+        threadProgram
+      )[0];
+
+    // token accounts are required, even when no tokens are transferred
+    const tokenAccounts = () => ({
+      receiver: user,
+      senderTokenAccount: tokenAccount.publicKey,
+      receiverTokenAccount: tokenAccount.publicKey,
+      mint: mint.publicKey,
+      tokenProgram: tokenProgram.programId,
+    });
+
+    const balance = (key: PublicKey) => provider.connection.getBalance(key);
+
+    const fee = async (signature: string) => {
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+      });
+      return tx.meta.fee;
+    };
+
+    const rateLimit = async () => {
+      const profile = await program.account.userProfile.fetch(UserProfilePDA);
+      const uid = Buffer.alloc(4);
+      uid.writeUInt32LE(profile.uid);
+      return pda(anchor.utils.bytes.utf8.encode("rate_limit"), uid);
+    };
+
+    before(async () => {
+      UserProfilePDA = await userProfilePDA();
+
+      await tokenProgram.methods
+        .initializeMint(0, user, null)
+        .accounts({ mint: mint.publicKey })
+        .preInstructions([
+          await tokenProgram.account.mint.createInstruction(mint),
+        ])
+        .signers([mint])
+        .rpc();
+
+      await tokenProgram.methods
+        .initializeAccount()
+        .accounts({
+          account: tokenAccount.publicKey,
+          mint: mint.publicKey,
+          authority: user,
+        })
+        .preInstructions([
+          await tokenProgram.account.token.createInstruction(tokenAccount),
+        ])
+        .signers([tokenAccount])
+        .rpc();
+
+      // the spling pays the reply subsidy, so it needs a balance
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: user,
+            toPubkey: SplingPDA,
+            lamports: 100_000_000,
+          })
+        )
+      );
+    });
+
+    it("Deletes a group profile", async () => {
+      await program.methods
+        .createGroupProfile(anchor.web3.Keypair.generate().publicKey, null)
+        .accounts({
+          user,
+          spling: SplingPDA,
+          groupProfile: GroupProfilePDA,
+          b: BankPDA,
+          ...tokenAccounts(),
+        })
+        .rpc({ commitment: "confirmed" });
+
+      const rent = await balance(GroupProfilePDA);
+      const before = await balance(user);
+
+      const signature = await program.methods
+        .deleteGroupProfile(anchor.web3.Keypair.generate().publicKey, null)
+        .accounts({
+          user,
+          spling: SplingPDA,
+          groupProfile: GroupProfilePDA,
+          b: BankPDA,
+          ...tokenAccounts(),
+        })
+        .rpc({ commitment: "confirmed" });
+
+      assert.equal(await balance(user), before + rent - (await fee(signature)));
+      assert.equal(await balance(GroupProfilePDA), 0);
+    });
+
+    it("Requests to follow despite a pre-funded request", async () => {
+      const follower = anchor.web3.Keypair.generate();
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: user,
+            toPubkey: follower.publicKey,
+            lamports: 100_000_000,
+          })
+        )
+      );
+
+      const spling = await program.account.spling.fetch(SplingPDA);
+      const followerUid = Buffer.alloc(4);
+      followerUid.writeUInt32LE(spling.users + 1);
+      const FollowerProfilePDA = pda(
+        anchor.utils.bytes.utf8.encode("user_profile"),
+        followerUid
+      );
+
+      await program.methods
+        .createUserProfile(anchor.web3.Keypair.generate().publicKey, null, null)
+        .accounts({
+          user: follower.publicKey,
+          spling: SplingPDA,
+          userProfile: FollowerProfilePDA,
+          wallet: pda(
+            anchor.utils.bytes.utf8.encode("wallet"),
+            follower.publicKey.toBuffer()
+          ),
+          b: BankPDA,
+        })
+        .signers([follower])
+        .rpc();
+
+      const profile = await program.account.userProfile.fetch(UserProfilePDA);
+      const uid = Buffer.alloc(4);
+      uid.writeUInt32LE(profile.uid);
+      const ProfileSettingsPDA = pda(
+        anchor.utils.bytes.utf8.encode("profile_settings"),
+        uid
+      );
+      const FollowRequestPDA = pda(
+        anchor.utils.bytes.utf8.encode("follow_request"),
+        uid,
+        followerUid
+      );
+
+      const protect = (protect: boolean) =>
+        program.methods
+          .setProtected(protect)
+          .accounts({
+            user,
+            userProfile: UserProfilePDA,
+            profileSettings: ProfileSettingsPDA,
+          })
+          .rpc();
+      await protect(true);
+
+      // lamports sent to the request address up front can't block the request
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: user,
+            toPubkey: FollowRequestPDA,
+            lamports: 1_000_000,
+          })
+        )
+      );
+
+      await program.methods
+        .followUser(profile.uid, null)
+        .accounts({
+          user: follower.publicKey,
+          spling: SplingPDA,
+          userProfile: FollowerProfilePDA,
+          targetSettings: ProfileSettingsPDA,
+          followRequest: FollowRequestPDA,
+          b: BankPDA,
+          ...tokenAccounts(),
+        })
+        .signers([follower])
+        .rpc();
+
+      const request = await program.account.followRequest.fetch(
+        FollowRequestPDA
+      );
+      assert.equal(request.uid, profile.uid);
+      assert.equal(request.follower, followerUid.readUInt32LE());
+
+      await protect(false);
+    });
+
+    it("Deletes a post", async () => {
+      const TagPDA = pda(
+        anchor.utils.bytes.utf8.encode("tag"),
+        Buffer.from("hello")
+      );
+      const tag = await program.account.tag.fetch(TagPDA);
+      const index = Buffer.alloc(4);
+      index.writeUInt32LE(tag.next);
+      const TagPostPDA = pda(
+        anchor.utils.bytes.utf8.encode("tag_post"),
+        TagPDA.toBuffer(),
+        index
+      );
+      const tagAccounts = [
+        { pubkey: TagPDA, isWritable: true, isSigner: false },
+        { pubkey: TagPostPDA, isWritable: true, isSigner: false },
+      ];
+
+      // funding the index entry address up front doesn't block the post
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: user,
+            toPubkey: TagPostPDA,
+            lamports: 1_000_000,
+          })
+        )
+      );
+
+      await program.methods
+        .submitPost(1, postShdw, ["hello"], [], null, null)
+        .accounts({
+          user,
+          spling: SplingPDA,
+          userProfile: UserProfilePDA,
+          config: ConfigPDA,
+          rateLimit: await rateLimit(),
+          post: PostPDA,
+          trending: TrendingPDA,
+          likes: LikesPDA,
+          b: BankPDA,
+          postThread: thread("post_thread"),
+          threadProgram,
+        })
+        .remainingAccounts(tagAccounts)
+        .rpc({ commitment: "confirmed" });
+
+      const rent =
+        (await balance(PostPDA)) +
+        (await balance(LikesPDA)) +
+        (await balance(TagPostPDA));
+      const splingBefore = await balance(SplingPDA);
+      const before = await balance(user);
+
+      const signature = await program.methods
+        .deletePost(1, postShdw, null)
+        .accounts({
+          user,
+          spling: SplingPDA,
+          userProfile: UserProfilePDA,
+          post: PostPDA,
+          likes: LikesPDA,
+          trending: TrendingPDA,
+          payer: user,
+          b: BankPDA,
+          ...tokenAccounts(),
+          postThread: thread("post_thread"),
+          publishThread: thread("publish_thread"),
+          pollThread: thread("poll_thread"),
+          threadProgram,
+        })
+        .remainingAccounts(tagAccounts)
+        .rpc({ commitment: "confirmed" });
+
+      assert.equal(await balance(user), before + rent - (await fee(signature)));
+      assert.equal(await balance(SplingPDA), splingBefore);
+      assert.equal(await balance(PostPDA), 0);
+      assert.equal(await balance(LikesPDA), 0);
+
+      // the tag index entry is closed, and the tag counts only live posts
+      assert.equal(await balance(TagPostPDA), 0);
+      const after = await program.account.tag.fetch(TagPDA);
+      assert.equal(after.posts, tag.posts);
+      assert.equal(after.next, tag.next + 1);
+    });
+
+    it("Expires a post and closes its thread", async () => {
+      const shdw = anchor.web3.Keypair.generate().publicKey;
+      const ExpiringPostPDA = pda(
+        anchor.utils.bytes.utf8.encode("post"),
+        shdw.toBuffer()
+      );
+      const ExpiringLikesPDA = pda(
+        anchor.utils.bytes.utf8.encode("likes"),
+        ExpiringPostPDA.toBuffer()
+      );
+      const [PostThread] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("thread"),
+          ExpiringPostPDA.toBuffer(),
+          Buffer.from("post_thread"),
+        ],
+        threadProgram
+      );
+
+      const expiry = Math.floor(Date.now() / 1000) + 2 * 60 * 60;
+      await program.methods
+        .submitPost(1, shdw, [], [], null, new anchor.BN(expiry))
+        .accounts({
+          user,
+          spling: SplingPDA,
+          userProfile: UserProfilePDA,
+          config: ConfigPDA,
+          rateLimit: await rateLimit(),
+          post: ExpiringPostPDA,
+          trending: TrendingPDA,
+          likes: ExpiringLikesPDA,
+          b: BankPDA,
+          postThread: PostThread,
+          threadProgram,
+        })
+        .rpc({ commitment: "confirmed" });
+
+      assert.isAbove(await balance(PostThread), 0);
+
+      // the expiry path can't run before the expiry
+      const expire = () =>
+        program.methods
+          .clockworkDeletePost()
+          .accounts({
+            shdw,
+            spling: SplingPDA,
+            post: ExpiringPostPDA,
+            likes: ExpiringLikesPDA,
+            trending: TrendingPDA,
+            payer: user,
+            authorProfile: UserProfilePDA,
+            postThread: PostThread,
+            threadProgram,
+          })
+          .rpc({ commitment: "confirmed" });
+      try {
+        await expire();
+        assert.fail("expired early");
+      } catch (err) {
+        assert.include(err.message, "PostNotExpired");
+      }
+
+      // deleting the post early also deletes its thread, and refunds both
+      const rent =
+        (await balance(ExpiringPostPDA)) +
+        (await balance(ExpiringLikesPDA)) +
+        (await balance(PostThread));
+      const before = await balance(user);
+      const profile = await program.account.userProfile.fetch(UserProfilePDA);
+
+      const signature = await program.methods
+        .deletePost(1, shdw, null)
+        .accounts({
+          user,
+          spling: SplingPDA,
+          userProfile: UserProfilePDA,
+          post: ExpiringPostPDA,
+          likes: ExpiringLikesPDA,
+          trending: TrendingPDA,
+          payer: user,
+          b: BankPDA,
+          ...tokenAccounts(),
+          postThread: PostThread,
+          publishThread: PublicKey.findProgramAddressSync(
+            [
+              Buffer.from("thread"),
+              ExpiringPostPDA.toBuffer(),
+              Buffer.from("publish_thread"),
+            ],
+            threadProgram
+          )[0],
+          pollThread: PublicKey.findProgramAddressSync(
+            [
+              Buffer.from("thread"),
+              ExpiringPostPDA.toBuffer(),
+              Buffer.from("poll_thread"),
+            ],
+            threadProgram
+          )[0],
+          threadProgram,
+        })
+        .rpc({ commitment: "confirmed" });
+
+      assert.equal(await balance(user), before + rent - (await fee(signature)));
+      assert.equal(await balance(PostThread), 0);
+
+      // the deleted post no longer counts towards the referral milestone
+      const after = await program.account.userProfile.fetch(UserProfilePDA);
+      assert.equal(after.livePosts, profile.livePosts - 1);
+      assert.equal(after.posts, profile.posts);
+    });
+
+    it("Publishes a scheduled post and closes its thread", async () => {
+      const shdw = anchor.web3.Keypair.generate().publicKey;
+      const ScheduledPostPDA = pda(
+        anchor.utils.bytes.utf8.encode("post"),
+        shdw.toBuffer()
+      );
+      const [PublishThread] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("thread"),
+          ScheduledPostPDA.toBuffer(),
+          Buffer.from("publish_thread"),
+        ],
+        threadProgram
+      );
+
+      const publishAt = Math.floor(Date.now() / 1000) + 65;
+      await program.methods
+        .schedulePost(1, shdw, [], [], new anchor.BN(publishAt))
+        .accounts({
+          user,
+          spling: SplingPDA,
+          userProfile: UserProfilePDA,
+          config: ConfigPDA,
+          rateLimit: await rateLimit(),
+          post: ScheduledPostPDA,
+          trending: TrendingPDA,
+          likes: pda(
+            anchor.utils.bytes.utf8.encode("likes"),
+            ScheduledPostPDA.toBuffer()
+          ),
+          publishThread: PublishThread,
+          threadProgram,
+        })
+        .rpc({ commitment: "confirmed" });
+
+      assert.isAbove(await balance(PublishThread), 0);
+
+      // wait for the scheduled time
+      await new Promise((resolve) => setTimeout(resolve, 75 * 1000));
+
+      // the thread publishes the post and leaves itself to close_post_thread,
+      // anyone else publishing it deletes the thread right away
+      const scheduled = await program.account.post.fetch(ScheduledPostPDA);
+      if (scheduled.st == 2) {
+        await program.methods
+          .publishPost()
+          .accounts({
+            shdw,
+            spling: SplingPDA,
+            post: ScheduledPostPDA,
+            trending: TrendingPDA,
+            payer: user,
+            publishThread: PublishThread,
+            threadProgram,
+          })
+          .rpc({ commitment: "confirmed" });
+      } else {
+        await program.methods
+          .closePostThread("publish_thread")
+          .accounts({
+            shdw,
+            post: ScheduledPostPDA,
+            payer: user,
+            thread: PublishThread,
+            threadProgram,
+          })
+          .rpc({ commitment: "confirmed" });
+      }
+
+      const post = await program.account.post.fetch(ScheduledPostPDA);
+      assert.equal(post.st, 1);
+      assert.isAbove(post.pid, 0);
+      assert.equal(await balance(PublishThread), 0);
+    });
+
+    it("Deletes a subsidized reply", async () => {
+      const [PostPDA] = await PublicKey.findProgramAddress(
+        [anchor.utils.bytes.utf8.encode("post"), shdw.publicKey.toBuffer()],
+        program.programId
+      );
+      const post = await program.account.post.fetch(PostPDA);
+      const author = Buffer.alloc(4);
+      author.writeUInt32LE(post.uid);
+
+      await program.methods
+        .submitReply(post.pid, replyShdw, null)
+        .accounts({
+          user,
+          spling: SplingPDA,
+          userProfile: UserProfilePDA,
+          config: ConfigPDA,
+          rateLimit: await rateLimit(),
+          post: PostPDA,
+          // replying to your own post, so protection doesn't apply
+          authorSettings: pda(
+            anchor.utils.bytes.utf8.encode("profile_settings"),
+            author
+          ),
+          followRequest: pda(
+            anchor.utils.bytes.utf8.encode("follow_request"),
+            author,
+            author
+          ),
+          authorProfile: UserProfilePDA,
+          rewardPool: RewardPoolPDA,
+          reply: ReplyPDA,
+          b: BankPDA,
+          ...tokenAccounts(),
+        })
+        .rpc({ commitment: "confirmed" });
+
+      const reply = await program.account.reply.fetch(ReplyPDA);
+      assert.ok(reply.payer.equals(SplingPDA));
+
+      const rent = await balance(ReplyPDA);
+      const splingBefore = await balance(SplingPDA);
+      const before = await balance(user);
+
+      const signature = await program.methods
+        .deleteReply(post.pid, replyShdw, null)
+        .accounts({
+          user,
+          spling: SplingPDA,
+          userProfile: UserProfilePDA,
+          reply: ReplyPDA,
+          payer: SplingPDA,
+          config: ConfigPDA,
+          authorProfile: UserProfilePDA,
+          rewardPool: RewardPoolPDA,
+          b: BankPDA,
+          ...tokenAccounts(),
+        })
+        .rpc({ commitment: "confirmed" });
+
+      assert.equal(await balance(SplingPDA), splingBefore + rent);
+      assert.equal(await balance(user), before - (await fee(signature)));
+      assert.equal(await balance(ReplyPDA), 0);
+    });
+
+    it("Sweeps a reply once its post is deleted", async () => {
+      const shdw = anchor.web3.Keypair.generate().publicKey;
+      const replyShdw = anchor.web3.Keypair.generate().publicKey;
+      const SweptPostPDA = pda(
+        anchor.utils.bytes.utf8.encode("post"),
+        shdw.toBuffer()
+      );
+      const SweptLikesPDA = pda(
+        anchor.utils.bytes.utf8.encode("likes"),
+        SweptPostPDA.toBuffer()
+      );
+      const SweptReplyPDA = pda(
+        anchor.utils.bytes.utf8.encode("reply"),
+        replyShdw.toBuffer()
+      );
+      const postThread = (name: string) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("thread"), SweptPostPDA.toBuffer(), Buffer.from(name)],
+          threadProgram
+        )[0];
+
+      const submitPost = async () =>
+        program.methods
+          .submitPost(1, shdw, [], [], null, null)
+          .accounts({
+            user,
+            spling: SplingPDA,
+            userProfile: UserProfilePDA,
+            config: ConfigPDA,
+            rateLimit: await rateLimit(),
+            post: SweptPostPDA,
+            trending: TrendingPDA,
+            likes: SweptLikesPDA,
+            b: BankPDA,
+            postThread: postThread("post_thread"),
+            threadProgram,
+          })
+          .rpc({ commitment: "confirmed" });
+      const deletePost = () =>
+        program.methods
+          .deletePost(1, shdw, null)
+          .accounts({
+            user,
+            spling: SplingPDA,
+            userProfile: UserProfilePDA,
+            post: SweptPostPDA,
+            likes: SweptLikesPDA,
+            trending: TrendingPDA,
+            payer: user,
+            b: BankPDA,
+            ...tokenAccounts(),
+            postThread: postThread("post_thread"),
+            publishThread: postThread("publish_thread"),
+            pollThread: postThread("poll_thread"),
+            threadProgram,
+          })
+          .rpc({ commitment: "confirmed" });
+
+      await submitPost();
+
+      const post = await program.account.post.fetch(SweptPostPDA);
+      const author = Buffer.alloc(4);
+      author.writeUInt32LE(post.uid);
+
+      await program.methods
+        .submitReply(post.pid, replyShdw, null)
+        .accounts({
+          user,
+          spling: SplingPDA,
+          userProfile: UserProfilePDA,
+          config: ConfigPDA,
+          rateLimit: await rateLimit(),
+          post: SweptPostPDA,
+          authorSettings: pda(
+            anchor.utils.bytes.utf8.encode("profile_settings"),
+            author
+          ),
+          followRequest: pda(
+            anchor.utils.bytes.utf8.encode("follow_request"),
+            author,
+            author
+          ),
+          authorProfile: UserProfilePDA,
+          rewardPool: RewardPoolPDA,
+          reply: SweptReplyPDA,
+          b: BankPDA,
+          ...tokenAccounts(),
+        })
+        .rpc({ commitment: "confirmed" });
+
+      const reply = await program.account.reply.fetch(SweptReplyPDA);
+      const sweep = () =>
+        program.methods
+          .sweepOrphanedReply(replyShdw)
+          .accounts({
+            reply: SweptReplyPDA,
+            post: SweptPostPDA,
+            payer: reply.payer,
+            config: ConfigPDA,
+            authorProfile: UserProfilePDA,
+            rewardPool: RewardPoolPDA,
+          })
+          .rpc({ commitment: "confirmed" });
+
+      // a reply of a live post can't be swept
+      try {
+        await sweep();
+        assert.fail("swept a live reply");
+      } catch (err) {
+        assert.include(err.message, "PostStillExists");
+      }
+
+      // a post created again at the same address doesn't keep the reply
+      await deletePost();
+      await submitPost();
+
+      const rent = await balance(SweptReplyPDA);
+      const before = await balance(reply.payer);
+      const signature = await sweep();
+
+      // the sweeper pays the fee, unless it also paid for the reply
+      const fees = reply.payer.equals(user) ? await fee(signature) : 0;
+      assert.equal(await balance(reply.payer), before + rent - fees);
+      assert.equal(await balance(SweptReplyPDA), 0);
+
+      await deletePost();
+    });
+
+    it("Deletes a user profile", async () => {
+      const WalletPDA = pda(
+        anchor.utils.bytes.utf8.encode("wallet"),
+        user.toBuffer()
+      );
+      const profile = await program.account.userProfile.fetch(UserProfilePDA);
+      const uid = Buffer.alloc(4);
+      uid.writeUInt32LE(profile.uid);
+      const StakePositionPDA = pda(
+        anchor.utils.bytes.utf8.encode("stake_position"),
+        uid
+      );
+      const deleteProfile = () =>
+        program.methods
+          .deleteUserProfile(0, anchor.web3.Keypair.generate().publicKey, null)
+          .accounts({
+            user,
+            spling: SplingPDA,
+            userProfile: UserProfilePDA,
+            wallet: WalletPDA,
+            stakePosition: StakePositionPDA,
+            b: BankPDA,
+            ...tokenAccounts(),
+          })
+          .rpc({ commitment: "confirmed" });
+
+      // the wallet accounts of other wallets would still point to the profile
+      const second = anchor.web3.Keypair.generate();
+      const SecondWalletPDA = pda(
+        anchor.utils.bytes.utf8.encode("wallet"),
+        second.publicKey.toBuffer()
+      );
+      await program.methods
+        .addWallet()
+        .accounts({
+          user,
+          newWallet: second.publicKey,
+          userProfile: UserProfilePDA,
+          wallet: SecondWalletPDA,
+        })
+        .signers([second])
+        .rpc();
+      try {
+        await deleteProfile();
+        assert.fail("deleted a profile with another wallet");
+      } catch (err) {
+        assert.include(err.message, "WalletsRemaining");
+      }
+      await program.methods
+        .removeWallet(second.publicKey)
+        .accounts({ user, userProfile: UserProfilePDA, wallet: SecondWalletPDA })
+        .rpc();
+
+      // the emptied stake position is closed with the profile
+      const rent =
+        (await balance(UserProfilePDA)) +
+        (await balance(WalletPDA)) +
+        (await balance(StakePositionPDA));
+      const before = await balance(user);
+
+      const signature = await deleteProfile();
+
+      assert.equal(await balance(user), before + rent - (await fee(signature)));
+      assert.equal(await balance(UserProfilePDA), 0);
+      assert.equal(await balance(WalletPDA), 0);
+      assert.equal(await balance(StakePositionPDA), 0);
+    });
+  });
 });